@@ -5,6 +5,7 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use criterion::{criterion_group, criterion_main, Criterion};
+use discv5::messages::Ping;
 use discv5::unpacking::{
     unpack, unpack_handshake_message, unpack_handshake_message_with_record,
     unpack_ordinary_message, unpack_whoareyou,
@@ -59,7 +60,7 @@ fn unpack_packet(c: &mut Criterion) {
             let (masking_iv, _, nonce, static_header, auth_data, encrypted_message_data) =
                 unpack(&dest_node_id, EXAMPLE_PING_MESSAGE_PACKET_DATA).unwrap();
 
-            let (_, _) = unpack_ordinary_message(
+            let (_, _) = unpack_ordinary_message::<Ping>(
                 &masking_iv,
                 &read_key,
                 &nonce,
@@ -85,7 +86,7 @@ fn unpack_packet(c: &mut Criterion) {
             let (masking_iv, _, nonce, static_header, auth_data, encrypted_message_data) =
                 unpack(&dest_node_id, EXAMPLE_PING_HANDSHAKE_PACKET).unwrap();
 
-            let (_, _, _, _) = unpack_handshake_message::<Schemev4>(
+            let (_, _, _, _) = unpack_handshake_message::<Schemev4, Ping>(
                 &masking_iv,
                 &read_key_handshake,
                 &nonce,
@@ -102,7 +103,7 @@ fn unpack_packet(c: &mut Criterion) {
             let (masking_iv, _, nonce, static_header, auth_data, encrypted_message_data) =
                 unpack(&dest_node_id, EXAMPLE_PING_HANDSHAKE_WITH_ENR_PACKET).unwrap();
 
-            let (_, _, _, _, _) = unpack_handshake_message_with_record::<Schemev4>(
+            let (_, _, _, _, _) = unpack_handshake_message_with_record::<Schemev4, Ping>(
                 &masking_iv,
                 &read_key_handshake_enr,
                 &nonce,
@@ -25,9 +25,9 @@ macro_rules! impl_decode_message {
     };
 }
 
-impl_decode_message!(decode_ping, Ping);
-impl_decode_message!(decode_pong, Pong);
-impl_decode_message!(decode_findnode, FindNode);
-impl_decode_message!(decode_nodes, Nodes);
-impl_decode_message!(decode_talkreq, TalkReq);
-impl_decode_message!(decode_talkresp, TalkResp);
+impl_decode_message!(decode_ping, Ping<'_>);
+impl_decode_message!(decode_pong, Pong<'_>);
+impl_decode_message!(decode_findnode, FindNode<'_>);
+impl_decode_message!(decode_nodes, Nodes<'_>);
+impl_decode_message!(decode_talkreq, TalkReq<'_>);
+impl_decode_message!(decode_talkresp, TalkResp<'_>);
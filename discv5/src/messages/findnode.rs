@@ -20,6 +20,17 @@ impl<'a> Message<'a> for FindNode<'a> {
     const MIN_DATA_BYTE_LENGTH: usize = 3; // see test `min_data_byte_length`
 }
 
+impl FindNode<'_> {
+    /// Converts `self` to a `FindNode` owning the bytes, copying them if
+    /// borrowed.
+    pub fn into_owned(self) -> FindNode<'static> {
+        FindNode {
+            request_id: self.request_id.into_owned(),
+            distances: self.distances,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::messages;
@@ -46,6 +57,24 @@ mod tests {
         assert_eq!(rlp::decode::<FindNode>(&encoded[1..]).unwrap(), findnode);
     }
 
+    #[test]
+    fn into_owned() {
+        let data = hex_literal::hex!("cd880102030405060708c3010203").to_vec();
+        let decoded = rlp::decode::<FindNode>(&data).unwrap();
+
+        let owned: FindNode<'static> = decoded.into_owned();
+        drop(data);
+        assert_eq!(owned.request_id.bytes(), [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(
+            owned.distances,
+            [
+                NodeProtocolDistance(1),
+                NodeProtocolDistance(2),
+                NodeProtocolDistance(3),
+            ]
+        );
+    }
+
     #[test]
     fn min_data_byte_length() {
         let message = FindNode {
@@ -20,6 +20,17 @@ impl<'a> Message<'a> for Ping<'a> {
     const MIN_DATA_BYTE_LENGTH: usize = 3; // see test `min_data_byte_length`
 }
 
+impl Ping<'_> {
+    /// Converts `self` to a `Ping` owning the bytes, copying them if
+    /// borrowed.
+    pub fn into_owned(self) -> Ping<'static> {
+        Ping {
+            request_id: self.request_id.into_owned(),
+            enr_seq: self.enr_seq,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::messages;
@@ -42,6 +53,17 @@ mod tests {
         assert_eq!(rlp::decode::<Ping>(&encoded[1..]).unwrap(), ping);
     }
 
+    #[test]
+    fn into_owned() {
+        let data = hex_literal::hex!("ca88010203040506070807").to_vec();
+        let decoded = rlp::decode::<Ping>(&data).unwrap();
+
+        let owned: Ping<'static> = decoded.into_owned();
+        drop(data);
+        assert_eq!(owned.request_id.bytes(), [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(owned.enr_seq, 7);
+    }
+
     #[test]
     fn min_data_byte_length() {
         let message = Ping {
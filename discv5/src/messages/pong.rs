@@ -24,6 +24,19 @@ impl<'a> Message<'a> for Pong<'a> {
     const MIN_DATA_BYTE_LENGTH: usize = 9; // see test `min_data_byte_length`
 }
 
+impl Pong<'_> {
+    /// Converts `self` to a `Pong` owning the bytes, copying them if
+    /// borrowed.
+    pub fn into_owned(self) -> Pong<'static> {
+        Pong {
+            request_id: self.request_id.into_owned(),
+            enr_seq: self.enr_seq,
+            recipient_ip: self.recipient_ip,
+            recipient_port: self.recipient_port,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::net::{Ipv4Addr, Ipv6Addr};
@@ -68,6 +81,19 @@ mod tests {
         assert_eq!(rlp::decode::<Pong>(&encoded[1..]).unwrap(), pong);
     }
 
+    #[test]
+    fn into_owned() {
+        let data = hex!("d288010203040506070807847f00000182ffff").to_vec();
+        let decoded = rlp::decode::<Pong>(&data).unwrap();
+
+        let owned: Pong<'static> = decoded.into_owned();
+        drop(data);
+        assert_eq!(owned.request_id.bytes(), [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(owned.enr_seq, 7);
+        assert_eq!(owned.recipient_ip, IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)));
+        assert_eq!(owned.recipient_port, u16::MAX);
+    }
+
     #[test]
     fn min_data_byte_length() {
         let message = Pong {
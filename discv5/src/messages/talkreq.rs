@@ -23,6 +23,18 @@ impl<'a> Message<'a> for TalkReq<'a> {
     const MIN_DATA_BYTE_LENGTH: usize = 4; // see test `min_data_byte_length`
 }
 
+impl TalkReq<'_> {
+    /// Converts `self` to a `TalkReq` owning the bytes, copying them if
+    /// borrowed.
+    pub fn into_owned(self) -> TalkReq<'static> {
+        TalkReq {
+            request_id: self.request_id.into_owned(),
+            protocol: Cow::Owned(self.protocol.into_owned()),
+            request: Cow::Owned(self.request.into_owned()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::messages;
@@ -48,6 +60,19 @@ mod tests {
         assert_eq!(rlp::decode::<TalkReq>(&encoded[1..]).unwrap(), talkreq);
     }
 
+    #[test]
+    fn into_owned() {
+        let data = hex_literal::hex!("d18801020304050607088301020383040506").to_vec();
+        let decoded = rlp::decode::<TalkReq>(&data).unwrap();
+        assert!(matches!(decoded.protocol, Cow::Borrowed(_)));
+
+        let owned: TalkReq<'static> = decoded.into_owned();
+        drop(data);
+        assert_eq!(owned.request_id.bytes(), [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(owned.protocol.as_ref(), [1, 2, 3]);
+        assert_eq!(owned.request.as_ref(), [4, 5, 6]);
+    }
+
     #[test]
     fn min_data_byte_length() {
         let message = TalkReq {
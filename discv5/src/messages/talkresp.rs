@@ -22,6 +22,17 @@ impl<'a> Message<'a> for TalkResp<'a> {
     const MIN_DATA_BYTE_LENGTH: usize = 3; // see test `min_data_byte_length`
 }
 
+impl TalkResp<'_> {
+    /// Converts `self` to a `TalkResp` owning the bytes, copying them if
+    /// borrowed.
+    pub fn into_owned(self) -> TalkResp<'static> {
+        TalkResp {
+            request_id: self.request_id.into_owned(),
+            response: Cow::Owned(self.response.into_owned()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::messages;
@@ -43,6 +54,18 @@ mod tests {
         assert_eq!(rlp::decode::<TalkResp>(&encoded[1..]).unwrap(), talkresp);
     }
 
+    #[test]
+    fn into_owned() {
+        let data = hex_literal::hex!("cd88010203040506070883070809").to_vec();
+        let decoded = rlp::decode::<TalkResp>(&data).unwrap();
+        assert!(matches!(decoded.response, Cow::Borrowed(_)));
+
+        let owned: TalkResp<'static> = decoded.into_owned();
+        drop(data);
+        assert_eq!(owned.request_id.bytes(), [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(owned.response.as_ref(), [7, 8, 9]);
+    }
+
     #[test]
     fn min_data_byte_length() {
         let message = TalkResp {
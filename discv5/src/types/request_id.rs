@@ -47,6 +47,12 @@ impl<'a> RequestId<'a> {
     pub fn bytes(&self) -> &[u8] {
         &self.0
     }
+
+    /// Converts `self` to a `RequestId` owning the bytes, copying them if
+    /// borrowed.
+    pub fn into_owned(self) -> RequestId<'static> {
+        RequestId(Cow::Owned(self.0.into_owned()))
+    }
 }
 
 const MAX_REQUEST_ID_BYTE_LENGTH: usize = 8;
@@ -104,7 +104,7 @@ pub fn unpack_with_record<'a, S: Scheme, M: Message<'a>>(
 #[allow(clippy::type_complexity)]
 fn unpack_authdata_fixed_part<S: Scheme>(
     bytes: &[u8],
-) -> Result<(NodeId, S::Signature, S::PublicKey, &[u8]), Error> {
+) -> Result<(NodeId<'_>, S::Signature, S::PublicKey, &[u8]), Error> {
     let fixed_part_len = size_of_handshake_message_authdata_fixed_part::<S>() as usize;
     debug_assert!(bytes.len() >= fixed_part_len);
 
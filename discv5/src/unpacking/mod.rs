@@ -9,6 +9,7 @@ mod handshake_message;
 mod ordinary_message;
 mod packet;
 mod static_header;
+#[allow(clippy::module_inception)]
 mod tests;
 mod whoareyou;
 
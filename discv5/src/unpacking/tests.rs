@@ -272,7 +272,7 @@ mod tests {
         bytes: &'a [u8],
     ) -> Result<(), Error> {
         let (masking_iv, flag, nonce, static_header, auth_data, encrypted_message_data) =
-            unpack(&dest_node_id, &bytes)?;
+            unpack(dest_node_id, bytes)?;
         if flag != Flag::OrdinaryMessage {
             return Err(Error::InvalidFlag);
         }
@@ -294,14 +294,14 @@ mod tests {
         bytes: &'a [u8],
     ) -> Result<(), Error> {
         let (masking_iv, flag, nonce, static_header, auth_data, encrypted_message_data) =
-            unpack(&dest_node_id, &bytes)?;
+            unpack(dest_node_id, bytes)?;
         if flag != Flag::HandshakeMessage {
             return Err(Error::InvalidFlag);
         }
 
         unpack_handshake_message::<S, M>(
             &masking_iv,
-            &read_key,
+            read_key,
             &nonce,
             &static_header,
             &auth_data,
@@ -316,14 +316,14 @@ mod tests {
         bytes: &'a [u8],
     ) -> Result<(), Error> {
         let (masking_iv, flag, nonce, static_header, auth_data, encrypted_message_data) =
-            unpack(&dest_node_id, &bytes)?;
+            unpack(dest_node_id, bytes)?;
         if flag != Flag::HandshakeMessage {
             return Err(Error::InvalidFlag);
         }
 
         unpack_handshake_message_with_record::<S, M>(
             &masking_iv,
-            &read_key,
+            read_key,
             &nonce,
             &static_header,
             &auth_data,
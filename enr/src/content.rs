@@ -8,6 +8,8 @@
 
 use std::net::{Ipv4Addr, Ipv6Addr};
 
+use rlp::ItemPayloadSlice;
+
 use crate::constants::SEQUENCE_NUMBER_INITIAL;
 use crate::SeqNum;
 
@@ -45,3 +47,7 @@ impl Content {
 /// Represents the RLP encoded form of a `Content`.
 #[derive(PartialEq)]
 pub(crate) struct ContentRlpEncoded(pub(crate) Vec<u8>);
+
+/// Represents the RLP list payload of a `Content`, i.e. its RLP encoded form
+/// without the list header, as found in the RLP encoded form of a `Record`.
+pub(crate) struct ContentRlpItems<'a>(pub(crate) ItemPayloadSlice<'a>);
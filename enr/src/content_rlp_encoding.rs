@@ -6,57 +6,86 @@
 
 //! Implements `Content` encoding to its RLP form.
 
-use rlp::{encode, encode_to, ItemPayloadSlice};
+use rlp::{Encode, ItemType, Output, RlpStream};
 
-use crate::content::{Content, ContentRlpEncoded};
+use crate::content::{Content, ContentRlpEncoded, ContentRlpItems};
 use crate::predefined_keys::{ID_KEY, IP4_KEY, IP6_KEY, TCP4_KEY, TCP6_KEY, UDP4_KEY, UDP6_KEY};
 use crate::Scheme;
 
 impl Content {
     /// Encodes `self` to its RLP encoded form.
     pub(crate) fn to_rlp_encoded<S: Scheme>(&self) -> ContentRlpEncoded {
-        let mut list_payload = vec![];
-        self.encode_to_rlp_list_payload::<S>(&mut list_payload);
-
-        let mut encoded = vec![];
-        ItemPayloadSlice(&list_payload).encode_as_list(&mut encoded);
-        ContentRlpEncoded(encoded)
+        let mut stream = RlpStream::new();
+        stream.begin_list();
+        self.encode_to_rlp_list_payload::<S>(&mut stream);
+        stream.end_list();
+        ContentRlpEncoded(stream.out())
     }
 
-    /// Encodes `self` to its RLP encoded form and appends the result to
-    /// `list_payload`.
-    pub(crate) fn encode_to_rlp_list_payload<S: Scheme>(&self, list_payload: &mut Vec<u8>) {
-        encode_to(&self.seq, list_payload);
+    /// Encodes `self` to its RLP encoded form and appends the items to the
+    /// open list of `stream`.
+    pub(crate) fn encode_to_rlp_list_payload<S: Scheme>(&self, stream: &mut RlpStream) {
+        stream.append(&self.seq);
+        for (key, value) in self.sorted_pairs::<S>() {
+            stream.append(&key);
+            value.append_to(stream);
+        }
+    }
 
-        // The key/value pairs must be sorted by key.
-        let mut pairs = vec![];
-        pairs.push((ID_KEY, encode(&self.id)));
+    /// Returns the key/value pairs sorted by key.
+    fn sorted_pairs<S: Scheme>(&self) -> Vec<(&'static [u8], &dyn EncodeValue)> {
+        let mut pairs: Vec<(&'static [u8], &dyn EncodeValue)> = vec![];
+        pairs.push((ID_KEY, &self.id));
         if let Some(ref ip4) = self.ip4 {
-            pairs.push((IP4_KEY, encode(ip4)));
+            pairs.push((IP4_KEY, ip4));
         }
         if let Some(ref ip6) = self.ip6 {
-            pairs.push((IP6_KEY, encode(ip6)));
+            pairs.push((IP6_KEY, ip6));
         }
         if let Some(ref public_key_bytes) = self.public_key_data {
-            pairs.push((S::key_of_public_key(), encode(public_key_bytes)));
+            pairs.push((S::key_of_public_key(), public_key_bytes));
         }
-        if let Some(tcp4) = self.tcp4 {
-            pairs.push((TCP4_KEY, encode(&tcp4)));
+        if let Some(ref tcp4) = self.tcp4 {
+            pairs.push((TCP4_KEY, tcp4));
         }
-        if let Some(tcp6) = self.tcp6 {
-            pairs.push((TCP6_KEY, encode(&tcp6)));
+        if let Some(ref tcp6) = self.tcp6 {
+            pairs.push((TCP6_KEY, tcp6));
         }
-        if let Some(udp4) = self.udp4 {
-            pairs.push((UDP4_KEY, encode(&udp4)));
+        if let Some(ref udp4) = self.udp4 {
+            pairs.push((UDP4_KEY, udp4));
         }
-        if let Some(udp6) = self.udp6 {
-            pairs.push((UDP6_KEY, encode(&udp6)));
+        if let Some(ref udp6) = self.udp6 {
+            pairs.push((UDP6_KEY, udp6));
         }
 
+        // The key/value pairs must be sorted by key.
         pairs.sort_by_key(|k| k.0);
-        for (key, rlp_bytes) in pairs {
-            encode_to(&key, list_payload);
-            list_payload.extend(rlp_bytes);
-        }
+        pairs
+    }
+}
+
+// `Encode` of the values into the content, usable as a trait object as
+// `Encode::encode_to` is generic.
+trait EncodeValue {
+    fn append_to(&self, stream: &mut RlpStream);
+}
+
+impl<T: Encode> EncodeValue for T {
+    fn append_to(&self, stream: &mut RlpStream) {
+        stream.append(self);
+    }
+}
+
+impl Encode for ContentRlpItems<'_> {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+        self.0.encode_as_list(output);
+    }
+
+    fn encoded_len(&self) -> usize {
+        self.0.encoded_len_as_list()
+    }
+
+    fn encoded_type() -> ItemType {
+        ItemType::List
     }
 }
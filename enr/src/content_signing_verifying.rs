@@ -6,7 +6,7 @@
 
 use sha3::{Digest, Keccak256};
 
-use crate::content::ContentRlpEncoded;
+use crate::content::{ContentRlpEncoded, ContentRlpItems};
 use crate::Scheme;
 
 impl ContentRlpEncoded {
@@ -18,17 +18,21 @@ impl ContentRlpEncoded {
         let hash = Keccak256::digest(&self.0);
         S::sign(&hash, private_key)
     }
+}
 
-    /// Verifies if `signature` is valid with a `ContentRlpEncoded` using
+impl ContentRlpItems<'_> {
+    /// Verifies if `signature` is valid with a `ContentRlpItems` using
     /// `public_key`.
     ///
+    /// The content is hashed as it is encoded, without being copied.
+    ///
     /// Returns `true` if the signature is valid.
     pub(crate) fn verify<S: Scheme>(
         &self,
         signature: &S::Signature,
         public_key: &S::PublicKey,
     ) -> Result<bool, S::Error> {
-        let hash = Keccak256::digest(&self.0);
+        let hash = rlp::hash::<Keccak256, _>(self);
         S::verify(&hash, signature, public_key)
     }
 }
@@ -26,6 +26,7 @@ mod scheme_v4;
 #[cfg(feature = "k256")]
 mod scheme_v4_k256;
 mod scheme_v4_secp256k1;
+#[allow(clippy::module_inception)]
 mod tests;
 mod textual_form;
 mod types;
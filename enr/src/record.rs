@@ -9,10 +9,10 @@
 use std::borrow::Cow;
 use std::net::{Ipv4Addr, Ipv6Addr};
 
-use rlp::{ItemDataSlice, ItemPayloadSlice};
+use rlp::ItemDataSlice;
 
 use crate::constants::MAX_RLP_ENCODED_BYTE_LENGTH;
-use crate::content::{Content, ContentRlpEncoded};
+use crate::content::{Content, ContentRlpItems};
 use crate::{Error, Scheme, SeqNum};
 
 /// Represents a node record.
@@ -51,22 +51,68 @@ impl<'a> RecordRlpEncoded<'a> {
     pub fn bytes(&self) -> &[u8] {
         &self.0
     }
+
+    /// Converts `self` to a `RecordRlpEncoded` owning the bytes, copying
+    /// them if borrowed.
+    pub fn into_owned(self) -> RecordRlpEncoded<'static> {
+        RecordRlpEncoded(Cow::Owned(self.0.into_owned()))
+    }
+}
+
+// Decodes the record item as it is, header included, like `rlp::RlpRaw`, so
+// it can be a field of the types deriving `rlp::Decode`.
+impl<'a> rlp::Decode<'a> for RecordRlpEncoded<'a> {
+    const TYPE: rlp::ItemType = rlp::ItemType::List;
+
+    fn decode(_payload: rlp::ItemPayloadSlice<'a>) -> Result<Self, rlp::Error> {
+        Err(rlp::Error::RawItemWithoutHeader)
+    }
+
+    fn decode_item(
+        item_type: rlp::ItemType,
+        item_data: ItemDataSlice<'a>,
+        _payload: rlp::ItemPayloadSlice<'a>,
+    ) -> Result<Self, rlp::Error> {
+        if item_type != Self::TYPE {
+            return Err(rlp::Error::ItemTypeDoesNotMatch);
+        }
+        RecordRlpEncoded::from_slice(item_data.0).map_err(|e| match e {
+            Error::MaximumRecordRlpEncodedByteLengthExceeded => {
+                rlp::Error::MaximumItemByteLengthExceeded
+            }
+            _ => rlp::Error::InvalidByteRepresentaion,
+        })
+    }
+}
+
+// Encodes the record as it is.
+impl rlp::Encode for RecordRlpEncoded<'_> {
+    fn encode_to<O: rlp::Output + ?Sized>(&self, output: &mut O) {
+        output.extend_from_slice(&self.0);
+    }
+
+    fn encoded_len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn encoded_type() -> rlp::ItemType {
+        rlp::ItemType::List
+    }
 }
 
 impl Record {
     /// Encodes a `Record` to its RLP encoded form.
-    pub fn to_rlp_encoded<S: Scheme>(&self) -> Result<RecordRlpEncoded, Error> {
-        let mut list_payload = vec![];
-        rlp::encode_to(&self.signature_data, &mut list_payload);
-        self.content
-            .encode_to_rlp_list_payload::<S>(&mut list_payload);
-
-        let mut encoded = vec![];
-        ItemPayloadSlice(&list_payload).encode_as_list(&mut encoded);
+    pub fn to_rlp_encoded<S: Scheme>(&self) -> Result<RecordRlpEncoded<'_>, Error> {
+        let mut stream = rlp::RlpStream::new();
+        stream.begin_list();
+        stream.append(&self.signature_data);
+        self.content.encode_to_rlp_list_payload::<S>(&mut stream);
+        stream.end_list();
+
+        let encoded = stream.out();
         if encoded.len() > MAX_RLP_ENCODED_BYTE_LENGTH {
             return Err(Error::MaximumRecordRlpEncodedByteLengthExceeded);
         }
-
         Ok(RecordRlpEncoded(encoded.into()))
     }
 
@@ -75,7 +121,9 @@ impl Record {
         let mut list_iter = ItemDataSlice(record_encoded.bytes())
             .list_iter()
             .map_err(Error::RlpDecodingError)?;
-        let signature_data: Vec<u8> = list_iter.next_item().map_err(Error::RlpDecodingError)?;
+        let signature_data: Vec<u8> = list_iter
+            .next_item()
+            .map_err(|e| Error::RlpDecodingError(e.in_field("Record", "signature")))?;
         if signature_data.len() != S::ENR_REQUIRED_SIGNATURE_BYTE_LENGTH {
             return Err(Error::SignatureDataWithInvalidByteLength);
         }
@@ -92,10 +140,7 @@ impl Record {
         let public_key = S::new_public_key_from_bytes(public_key_data)
             .map_err(|e| Error::InvalidPublicKeyData(format!("{e}")))?;
 
-        let mut output = vec![];
-        content_rlp_items.encode_as_list(&mut output);
-        let content_encoded = ContentRlpEncoded(output);
-        let result = content_encoded
+        let result = ContentRlpItems(content_rlp_items)
             .verify::<S>(&signature, &public_key)
             .map_err(|e| Error::SignatureVerifyingFailed(format!("{e}")))?;
         if !result {
@@ -93,7 +93,7 @@ pub trait Scheme {
     ///
     /// To derive a node id(address), take the keccak256 hash of the
     /// uncompressed public key.
-    fn new_node_id(public_key: &Self::PublicKey) -> NodeId;
+    fn new_node_id(public_key: &Self::PublicKey) -> NodeId<'_>;
 
     /// Creates a shared secret through Diffie-Hellman key agreement
     ///
@@ -103,7 +103,7 @@ impl Scheme for Schemev4Secp256k1 {
         }
     }
 
-    fn new_node_id(public_key: &Self::PublicKey) -> NodeId {
+    fn new_node_id(public_key: &Self::PublicKey) -> NodeId<'_> {
         let uncompressed = &public_key.serialize_uncompressed()[1..];
         NodeId::from_array(Keccak256::digest(uncompressed).into())
     }
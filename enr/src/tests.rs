@@ -113,7 +113,7 @@ mod tests {
             ),
         ];
 
-        let private_key = S::new_private_key_from_bytes(&PRIVATE_KEY_DATA).unwrap();
+        let private_key = S::new_private_key_from_bytes(PRIVATE_KEY_DATA).unwrap();
         let scheme_keypair = SchemeKeyPair::from_private_key(private_key);
 
         for (test_name, test_from_address_only, content_data, address) in test_data {
@@ -31,4 +31,10 @@ impl<'a> NodeId<'a> {
     pub fn bytes(&self) -> &[u8; 32] {
         &self.0
     }
+
+    /// Converts `self` to a `NodeId` owning the byte array, copying it if
+    /// borrowed.
+    pub fn into_owned(self) -> NodeId<'static> {
+        NodeId(Cow::Owned(self.0.into_owned()))
+    }
 }
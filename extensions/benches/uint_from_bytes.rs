@@ -83,7 +83,7 @@ fn new_u8_from_be_bytes_with_left_padding_copy(bytes: &[u8]) -> u8 {
 
 #[inline]
 fn new_u16_from_be_bytes_with_left_padding_copy(bytes: &[u8]) -> u16 {
-    assert!(bytes.len() > 0 && bytes.len() <= size_of::<u16>());
+    assert!(!bytes.is_empty() && bytes.len() <= size_of::<u16>());
 
     let mut n_bytes = [0; size_of::<u16>()];
     n_bytes[(size_of::<u16>() - bytes.len())..].copy_from_slice(bytes);
@@ -92,7 +92,7 @@ fn new_u16_from_be_bytes_with_left_padding_copy(bytes: &[u8]) -> u16 {
 
 #[inline]
 fn new_u32_from_be_bytes_with_left_padding_copy(bytes: &[u8]) -> u32 {
-    assert!(bytes.len() > 0 && bytes.len() <= size_of::<u32>());
+    assert!(!bytes.is_empty() && bytes.len() <= size_of::<u32>());
 
     let mut n_bytes = [0; size_of::<u32>()];
     n_bytes[(size_of::<u32>() - bytes.len())..].copy_from_slice(bytes);
@@ -101,7 +101,7 @@ fn new_u32_from_be_bytes_with_left_padding_copy(bytes: &[u8]) -> u32 {
 
 #[inline]
 fn new_u64_from_be_bytes_with_left_padding_copy(bytes: &[u8]) -> u64 {
-    assert!(bytes.len() > 0 && bytes.len() <= size_of::<u64>());
+    assert!(!bytes.is_empty() && bytes.len() <= size_of::<u64>());
 
     let mut n_bytes = [0; size_of::<u64>()];
     n_bytes[(size_of::<u64>() - bytes.len())..].copy_from_slice(bytes);
@@ -94,6 +94,28 @@ pub fn new_u64_from_be_bytes_with_left_padding(bytes: &[u8]) -> u64 {
     }
 }
 
+/// Create a native endian integer value from its representation as a byte
+/// array in big endian.
+///
+/// The length of `bytes` must be in [1, 16]. If the length is less than 16,
+/// `bytes` will first be left padded with zeroes.
+///
+/// # Panics
+///
+/// Will panic if the length of `bytes` isn't in the range.
+/// ```
+#[inline]
+pub fn new_u128_from_be_bytes_with_left_padding(bytes: &[u8]) -> u128 {
+    assert!(
+        !bytes.is_empty() && bytes.len() <= 16,
+        "invalid byte length, expecting [1, 16]"
+    );
+
+    let mut byte_array = [0; 16];
+    byte_array[16 - bytes.len()..].copy_from_slice(bytes);
+    u128::from_be_bytes(byte_array)
+}
+
 #[cfg(test)]
 mod new_u8_tests {
     use super::*;
@@ -207,3 +229,32 @@ mod new_u64_tests {
         new_u64_from_be_bytes_with_left_padding(&[1, 2, 3, 4, 5, 6, 7, 8, 9]);
     }
 }
+
+#[cfg(test)]
+mod new_u128_tests {
+    use super::*;
+
+    #[test]
+    fn from_be_bytes() {
+        let data = [
+            (&[9_u8] as &[u8], 9),
+            (&[8, 9], 0x0809),
+            (&[1; 16], 0x01010101_01010101_01010101_01010101),
+        ];
+        for (bytes, n) in data {
+            assert_eq!(new_u128_from_be_bytes_with_left_padding(bytes), n);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_0_bytes() {
+        new_u128_from_be_bytes_with_left_padding(&[]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_17_bytes() {
+        new_u128_from_be_bytes_with_left_padding(&[1; 17]);
+    }
+}
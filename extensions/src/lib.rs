@@ -6,6 +6,7 @@
 
 //! Helper functions extend the standard library.
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 
 mod bytes;
@@ -6,7 +6,96 @@
 
 use criterion::{criterion_group, criterion_main, Criterion};
 
-use rlp::encode;
+use rlp::{encode, encode_to, Encode, ItemPayloadSlice, Output};
+
+/// Encodes `Vec<T>` into a temporary payload buffer, which is copied behind
+/// the list header, as the encoding did before `Encode::encoded_len`.
+struct Buffered<T>(Vec<T>);
+
+impl<T: Encode> Encode for Buffered<T> {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+        let mut payload = vec![];
+        self.0.iter().for_each(|element| {
+            encode_to(element, &mut payload);
+        });
+        ItemPayloadSlice(&payload).encode_as_list(output);
+    }
+}
+
+/// Encodes `value` into a growing `Vec`, as `encode` did before
+/// `Encode::encoded_len`.
+fn encode_buffered<T: Encode>(value: &T) -> Vec<u8> {
+    let mut output = vec![];
+    encode_to(value, &mut output);
+    output
+}
+
+#[derive(Encode)]
+struct Header {
+    parent_hash: [u8; 32],
+    number: u64,
+    gas_limit: u64,
+    extra_data: Vec<u8>,
+}
+
+#[derive(Encode)]
+struct Block {
+    header: Header,
+    transactions: Vec<Vec<u8>>,
+    ommers: Vec<Header>,
+}
+
+/// Encodes `Header` as the derive did before `Encode::encoded_len`.
+struct BufferedHeader<'a>(&'a Header);
+
+impl Encode for BufferedHeader<'_> {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+        let mut payload = vec![];
+        encode_to(&self.0.parent_hash, &mut payload);
+        encode_to(&self.0.number, &mut payload);
+        encode_to(&self.0.gas_limit, &mut payload);
+        encode_to(&self.0.extra_data, &mut payload);
+        ItemPayloadSlice(&payload).encode_as_list(output);
+    }
+}
+
+/// Encodes `Block` as the derive did before `Encode::encoded_len`.
+struct BufferedBlock<'a>(&'a Block);
+
+impl Encode for BufferedBlock<'_> {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+        let mut payload = vec![];
+        encode_to(&BufferedHeader(&self.0.header), &mut payload);
+
+        let mut transactions = vec![];
+        self.0.transactions.iter().for_each(|transaction| {
+            encode_to(transaction, &mut transactions);
+        });
+        ItemPayloadSlice(&transactions).encode_as_list(&mut payload);
+
+        let mut ommers = vec![];
+        self.0.ommers.iter().for_each(|ommer| {
+            encode_to(&BufferedHeader(ommer), &mut ommers);
+        });
+        ItemPayloadSlice(&ommers).encode_as_list(&mut payload);
+
+        ItemPayloadSlice(&payload).encode_as_list(output);
+    }
+}
+
+fn new_block() -> Block {
+    let header = || Header {
+        parent_hash: [0xab; 32],
+        number: 0x1023_4567,
+        gas_limit: 30_000_000,
+        extra_data: vec![0xcd; 32],
+    };
+    Block {
+        header: header(),
+        transactions: (0..200).map(|i| vec![i as u8; 120]).collect(),
+        ommers: (0..2).map(|_| header()).collect(),
+    }
+}
 
 fn encode_bench(c: &mut Criterion) {
     c.bench_function("encode_u64", |b| {
@@ -30,6 +119,66 @@ fn encode_bench(c: &mut Criterion) {
             encode(&data);
         })
     });
+
+    c.bench_function("encode_nested_vec_of_u64", |b| {
+        let data: Vec<Vec<Vec<u64>>> = (0..10)
+            .map(|_| (0..10).map(|_| (0u64..100).collect()).collect())
+            .collect();
+
+        b.iter(|| {
+            encode(&data);
+        })
+    });
+
+    c.bench_function("encode_nested_vec_of_u64_buffered", |b| {
+        let data: Buffered<Buffered<Buffered<u64>>> = Buffered(
+            (0..10)
+                .map(|_| Buffered((0..10).map(|_| Buffered((0u64..100).collect())).collect()))
+                .collect(),
+        );
+
+        b.iter(|| {
+            encode_buffered(&data);
+        })
+    });
+
+    c.bench_function("encode_nested_vec_of_bytes", |b| {
+        let data: Vec<Vec<Vec<u8>>> = (0..10)
+            .map(|_| (0..10).map(|i| vec![i as u8; 120]).collect())
+            .collect();
+
+        b.iter(|| {
+            encode(&data);
+        })
+    });
+
+    c.bench_function("encode_nested_vec_of_bytes_buffered", |b| {
+        let data: Buffered<Buffered<Vec<u8>>> = Buffered(
+            (0..10)
+                .map(|_| Buffered((0..10).map(|i| vec![i as u8; 120]).collect()))
+                .collect(),
+        );
+
+        b.iter(|| {
+            encode_buffered(&data);
+        })
+    });
+
+    c.bench_function("encode_derived_block", |b| {
+        let block = new_block();
+
+        b.iter(|| {
+            encode(&block);
+        })
+    });
+
+    c.bench_function("encode_derived_block_buffered", |b| {
+        let block = new_block();
+
+        b.iter(|| {
+            encode_buffered(&BufferedBlock(&block));
+        })
+    });
 }
 
 criterion_group!(benches, encode_bench);
@@ -0,0 +1,408 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements a serde `Deserializer` from RLP.
+
+use alloc::format;
+use alloc::string::ToString;
+
+use serde::de::{self, Deserialize, DeserializeSeed, IntoDeserializer, Visitor};
+
+use crate::{
+    decode_payload, Decode, Error, ItemDataSlice, ItemPayloadSlice, ItemType, ListIter, U8,
+};
+
+/// Deserializes RLP `data` to a `T`.
+///
+/// The values are mapped as described in `to_vec`. The items are checked to
+/// be canonical the same way as `rlp::decode`, and the lists of structs,
+/// tuples and enum variants to have the exact number of items.
+///
+/// # Examples
+///
+/// ```
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// struct Entry<'a> {
+///     id: u16,
+///     name: &'a str,
+/// }
+///
+/// let data = [0xc5, 0x01, 0x83, b'c', b'a', b't'];
+/// let entry: Entry = rlp::from_slice(&data).unwrap();
+/// assert_eq!(entry, Entry { id: 1, name: "cat" });
+/// ```
+pub fn from_slice<'a, T: Deserialize<'a>>(data: &'a [u8]) -> Result<T, Error> {
+    let deserializer = Deserializer::from_item_data(ItemDataSlice(data))?;
+    T::deserialize(deserializer)
+}
+
+/// A serde `Deserializer` of a RLP item.
+#[derive(Clone, Copy)]
+struct Deserializer<'de> {
+    item_type: ItemType,
+    payload: ItemPayloadSlice<'de>,
+}
+
+impl<'de> Deserializer<'de> {
+    fn from_item_data(item_data: ItemDataSlice<'de>) -> Result<Self, Error> {
+        let (item_type, payload) = item_data.as_payload()?;
+        Ok(Deserializer { item_type, payload })
+    }
+
+    fn decode<T: Decode<'de>>(self) -> Result<T, Error> {
+        decode_payload(self.item_type, self.payload)
+    }
+
+    fn list_iter(self) -> Result<ListIter<'de>, Error> {
+        if self.item_type != ItemType::List {
+            return Err(Error::ItemTypeDoesNotMatch);
+        }
+        Ok(self.payload.list_iter_unchecked())
+    }
+
+    // Visits the items of the list, which must all be visited.
+    fn visit_list<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let mut seq = SeqAccess {
+            list_iter: self.list_iter()?,
+            index: 0,
+        };
+        let value = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(value)
+    }
+}
+
+fn unsupported(type_name: &str) -> Error {
+    Error::Serde(format!("{type_name} is not supported by RLP"))
+}
+
+// Returns a `Deserializer` of the next item of `list_iter`.
+fn next_deserializer<'de>(
+    list_iter: &mut ListIter<'de>,
+) -> Option<Result<Deserializer<'de>, Error>> {
+    list_iter
+        .next()
+        .map(|result| result.map(|(item_type, payload)| Deserializer { item_type, payload }))
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.item_type {
+            ItemType::SingleValue => visitor.visit_borrowed_bytes(self.payload.0),
+            ItemType::List => self.visit_list(visitor),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_bool(self.decode()?)
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+        Err(unsupported("i8"))
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+        Err(unsupported("i16"))
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+        Err(unsupported("i32"))
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+        Err(unsupported("i64"))
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_u8(self.decode::<U8>()?.0)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_u16(self.decode()?)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_u32(self.decode()?)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_u64(self.decode()?)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+        Err(unsupported("f32"))
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+        Err(unsupported("f64"))
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let mut chars = self.decode::<&str>()?.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => visitor.visit_char(c),
+            _ => Err(Error::InvalidByteRepresentaion),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_borrowed_str(self.decode()?)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_borrowed_bytes(self.decode()?)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    // The empty single value is `None`, as `Option<T>` of the prelude.
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.item_type == ItemType::SingleValue && self.payload.0.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.list_iter()?.next().is_some() {
+            return Err(Error::ListDecodingNumberDoesNotMatch);
+        }
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.visit_list(visitor)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.visit_list(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.visit_list(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_map(MapAccess {
+            list_iter: self.list_iter()?,
+            value: None,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.visit_list(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let mut list_iter = self.list_iter()?;
+        let variant_index: u32 = list_iter.next_item()?;
+        visitor.visit_enum(EnumAccess {
+            variant_index,
+            list_iter,
+        })
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Accesses the items of a list as a sequence.
+struct SeqAccess<'de> {
+    list_iter: ListIter<'de>,
+    // The index of the next item.
+    index: usize,
+}
+
+impl<'de> SeqAccess<'de> {
+    // Checks all the items have been accessed.
+    fn end(mut self) -> Result<(), Error> {
+        if self.list_iter.next().is_some() {
+            return Err(Error::ListDecodingNumberDoesNotMatch);
+        }
+        Ok(())
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        let index = self.index;
+        self.index += 1;
+        next_deserializer(&mut self.list_iter)
+            .map(|result| result.and_then(|deserializer| seed.deserialize(deserializer)))
+            .transpose()
+            .map_err(|e| e.in_list_item(index))
+    }
+}
+
+/// Accesses the `[key, value]` lists of a list as a map.
+struct MapAccess<'de> {
+    list_iter: ListIter<'de>,
+    // The value of the last entry whose key has been accessed.
+    value: Option<Deserializer<'de>>,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let entry = match next_deserializer(&mut self.list_iter) {
+            Some(result) => result?,
+            None => return Ok(None),
+        };
+        let mut entry_iter = entry.list_iter()?;
+        let (key, value) = match (
+            next_deserializer(&mut entry_iter),
+            next_deserializer(&mut entry_iter),
+            entry_iter.next(),
+        ) {
+            (Some(key), Some(value), None) => (key?, value?),
+            _ => return Err(Error::ListDecodingNumberDoesNotMatch),
+        };
+
+        self.value = Some(value);
+        seed.deserialize(key).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| Error::Serde("map value accessed before its key".to_string()))?;
+        seed.deserialize(value)
+    }
+}
+
+/// Accesses the `[index, value]` list of an enum variant.
+struct EnumAccess<'de> {
+    variant_index: u32,
+    // The items following the index.
+    list_iter: ListIter<'de>,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess<'de> {
+    type Error = Error;
+    type Variant = SeqAccess<'de>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, SeqAccess<'de>), Error> {
+        let deserializer: de::value::U32Deserializer<Error> =
+            self.variant_index.into_deserializer();
+        let variant = seed.deserialize(deserializer)?;
+        Ok((
+            variant,
+            SeqAccess {
+                list_iter: self.list_iter,
+                index: 1,
+            },
+        ))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for SeqAccess<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        self.end()
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(mut self, seed: T) -> Result<T::Value, Error> {
+        let value = de::SeqAccess::next_element_seed(&mut self, seed)?
+            .ok_or(Error::ListDecodingIterationEnded)?;
+        self.end()?;
+        Ok(value)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        self.newtype_variant_seed(TupleSeed { len, visitor })
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.tuple_variant(fields.len(), visitor)
+    }
+}
+
+/// Deserializes the list of the fields of a tuple or struct variant.
+struct TupleSeed<V> {
+    len: usize,
+    visitor: V,
+}
+
+impl<'de, V: Visitor<'de>> DeserializeSeed<'de> for TupleSeed<V> {
+    type Value = V::Value;
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<V::Value, D::Error> {
+        deserializer.deserialize_tuple(self.len, self.visitor)
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements `DecodeOptions`.
+
+use crate::{Error, ItemDataSlice, ItemPayloadSlice, ItemType, ListIter};
+
+/// The default maximum length in bytes of an item, see
+/// `Limits::max_byte_length` and `StreamDecoder::with_max_item_byte_length`.
+pub const DEFAULT_MAX_ITEM_BYTE_LENGTH: usize = 16 * 1024 * 1024;
+
+/// Options of decoding, see `decode_with_options`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DecodeOptions {
+    limits: Limits,
+}
+
+impl DecodeOptions {
+    /// Creates a `DecodeOptions` with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the limits on the decoding data.
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Returns the limits on the decoding data.
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+}
+
+/// Limits on the decoding data, against malicious input.
+///
+/// The limits are checked on the structure of the data in a pass before
+/// decoding it, so the decoding types don't have to be aware of them, see
+/// `decode_with_options` for its cost.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Limits {
+    /// The maximum nesting depth of lists, the top-level list is at depth 1.
+    pub max_depth: usize,
+    /// The maximum number of items in a list.
+    pub max_list_items: usize,
+    /// The maximum length in bytes of the data.
+    pub max_byte_length: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_depth: 32,
+            max_list_items: 65536,
+            max_byte_length: DEFAULT_MAX_ITEM_BYTE_LENGTH,
+        }
+    }
+}
+
+/// A limit of `Limits`, returned with `Error::LimitExceeded`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Limit {
+    /// `Limits::max_depth`
+    Depth,
+    /// `Limits::max_list_items`
+    ListItems,
+    /// `Limits::max_byte_length`
+    ByteLength,
+}
+
+impl Limits {
+    /// Checks the item `data` doesn't exceed the limits.
+    ///
+    /// Only the headers of the items are decoded.
+    pub(crate) fn check(&self, data: &[u8]) -> Result<(), Error> {
+        if data.len() > self.max_byte_length {
+            return Err(Error::LimitExceeded(Limit::ByteLength));
+        }
+
+        let (item_type, payload) = ItemDataSlice(data).as_payload()?;
+        self.check_item(item_type, payload, 0)
+    }
+
+    // Checks the item of `item_type` and `payload`, whose parent list is at
+    // `depth`.
+    fn check_item(
+        &self,
+        item_type: ItemType,
+        payload: ItemPayloadSlice,
+        depth: usize,
+    ) -> Result<(), Error> {
+        if item_type == ItemType::SingleValue {
+            return Ok(());
+        }
+
+        // Stops before recursing any deeper than `max_depth`.
+        let depth = depth + 1;
+        if depth > self.max_depth {
+            return Err(Error::LimitExceeded(Limit::Depth));
+        }
+
+        for (count, result) in ListIter::from_list_payload_strict(payload).enumerate() {
+            if count == self.max_list_items {
+                return Err(Error::LimitExceeded(Limit::ListItems));
+            }
+
+            let (item_type, item_payload) = result?;
+            self.check_item(item_type, item_payload, depth)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use super::*;
+
+    const LIMITS: Limits = Limits {
+        max_depth: 2,
+        max_list_items: 3,
+        max_byte_length: 8,
+    };
+
+    #[test]
+    fn test_within_limits() {
+        let test_data = [
+            &hex!("80") as &[u8],
+            &hex!("c0"),
+            // [1, 2, 3]
+            &hex!("c3010203"),
+            // [[1, 2, 3]]
+            &hex!("c4c3010203"),
+            // [[], [], []]
+            &hex!("c3c0c0c0"),
+        ];
+
+        for data in test_data {
+            assert_eq!(LIMITS.check(data), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_limits_exceeded() {
+        let test_data = [
+            // [[[]]]
+            (&hex!("c2c1c0") as &[u8], Limit::Depth),
+            // [1, 2, 3, 4]
+            (&hex!("c401020304"), Limit::ListItems),
+            // [[1, 2, 3, 4]]
+            (&hex!("c5c401020304"), Limit::ListItems),
+            (&hex!("880102030405060708"), Limit::ByteLength),
+        ];
+
+        for (data, limit) in test_data {
+            assert_eq!(LIMITS.check(data), Err(Error::LimitExceeded(limit)));
+        }
+    }
+
+    #[test]
+    fn test_malformed_items() {
+        assert_eq!(
+            LIMITS.check(&hex!("c3c30102")),
+            Err(Error::ItemDataWithInvalidByteLength)
+        );
+    }
+}
@@ -6,7 +6,7 @@
 
 //! Provides convenience functions for RLP decoding.
 
-use crate::{Decode, Error, ItemDataSlice, ItemPayloadSlice, ItemType};
+use crate::{Decode, DecodeOptions, Error, ItemDataSlice, ItemPayloadSlice, ItemType};
 
 /// Decodes `data` to a `T`.
 ///
@@ -20,8 +20,44 @@ use crate::{Decode, Error, ItemDataSlice, ItemPayloadSlice, ItemType};
 /// ```
 #[inline]
 pub fn decode<'a, T: Decode<'a>>(data: &'a [u8]) -> Result<T, Error> {
-    let (item_type, payload) = ItemDataSlice(data).as_payload()?;
-    decode_payload(item_type, payload)
+    let (item_type, payload) = ItemDataSlice(data).as_payload_with(true)?;
+    T::decode_item(item_type, ItemDataSlice(data), payload)
+}
+
+/// Decodes `data` to a `T` with `options`.
+///
+/// The structure of `data` is checked against `options.limits()` before
+/// decoding, returning `Err(Error::LimitExceeded(_))` if a limit is exceeded.
+/// Use it instead of `decode` for untrusted data.
+///
+/// The check is a separate pass over `data`, decoding the header of every
+/// item, so the headers are decoded twice. It costs about as much as
+/// `validate`, typically a fraction of the decoding for the types copying or
+/// converting the payloads, but it can double the time of borrowing types,
+/// e.g. `RlpView` or `&[u8]`, on data with many small items.
+///
+/// # Examples
+///
+/// ```
+/// use rlp::{decode_with_options, DecodeOptions, Error, Limit, Limits};
+///
+/// let options = DecodeOptions::new().with_limits(Limits {
+///     max_list_items: 2,
+///     ..Limits::default()
+/// });
+///
+/// let value: Vec<u64> = decode_with_options(&[0xc2, 0x01, 0x02], &options).unwrap();
+/// assert_eq!(value, [1, 2]);
+///
+/// let result = decode_with_options::<Vec<u64>>(&[0xc3, 0x01, 0x02, 0x03], &options);
+/// assert_eq!(result.unwrap_err(), Error::LimitExceeded(Limit::ListItems));
+/// ```
+pub fn decode_with_options<'a, T: Decode<'a>>(
+    data: &'a [u8],
+    options: &DecodeOptions,
+) -> Result<T, Error> {
+    options.limits().check(data)?;
+    decode(data)
 }
 
 /// Decodes `payload` to a `T`.
@@ -17,9 +17,20 @@ impl<'a> ItemDataSlice<'a> {
     /// Decodes the head part of the data, and returns the type and payload of
     /// the item.
     ///
-    /// The returned payload is a subslice of the data.
+    /// The returned payload is a subslice of the data. The non-canonical
+    /// headers are rejected, even inside `decode_lenient`.
     pub fn as_payload(&self) -> Result<(ItemType, ItemPayloadSlice<'a>), Error> {
-        let (item_type, header_byte_length, payload_byte_length) = decode_header_unchecked(self.0)?;
+        self.as_payload_with(false)
+    }
+
+    // Like `as_payload`, accepting the non-canonical headers inside
+    // `decode_lenient` if `lenient` is true.
+    #[inline]
+    pub(crate) fn as_payload_with(
+        &self,
+        lenient: bool,
+    ) -> Result<(ItemType, ItemPayloadSlice<'a>), Error> {
+        let (item_type, header_byte_length, payload_byte_length) = decode_header(self.0, lenient)?;
         if (self.0.len() - header_byte_length as usize) != payload_byte_length as usize {
             return Err(Error::ItemDataWithInvalidByteLength);
         }
@@ -34,9 +45,20 @@ impl<'a> ItemDataSlice<'a> {
 /// Decodes the header part of `data`, and returns the type, header length,
 /// and payload length of the item.
 ///
-/// Doesn't check that the length of `data` is valid.
+/// Doesn't check that the length of `data` is valid. The non-canonical
+/// headers are rejected, even inside `decode_lenient`.
 pub fn decode_header_unchecked(
     data: &[u8],
+) -> Result<(ItemType, HeaderByteLength, PayloadByteLength), Error> {
+    decode_header(data, false)
+}
+
+// Like `decode_header_unchecked`, accepting the non-canonical headers inside
+// `decode_lenient` if `lenient` is true.
+#[inline]
+pub(crate) fn decode_header(
+    data: &[u8],
+    lenient: bool,
 ) -> Result<(ItemType, HeaderByteLength, PayloadByteLength), Error> {
     if data.is_empty() {
         return Err(Error::EmptyData);
@@ -60,7 +82,7 @@ pub fn decode_header_unchecked(
                 // Single byte (in [0x00, 0x7f]) encoded as two is invalid.
                 // https://github.com/paritytech/parity-common/issues/49
                 if data[1] < 0x80 {
-                    return Err(Error::SingleByteEncodedAsTwo);
+                    reject_non_canonical(Error::SingleByteEncodedAsTwo, data, lenient)?;
                 }
             }
             Ok((ItemType::SingleValue, 1, payload_byte_length))
@@ -80,7 +102,7 @@ pub fn decode_header_unchecked(
             );
             // Short string (0-55 bytes) encoded as long is invalid.
             if payload_byte_length < 56 {
-                return Err(Error::ShortStringEncodedAsLong);
+                reject_non_canonical(Error::ShortStringEncodedAsLong, data, lenient)?;
             }
             Ok((
                 ItemType::SingleValue,
@@ -112,7 +134,7 @@ pub fn decode_header_unchecked(
             );
             // Short list (0-55 bytes) encoded as long is invalid.
             if payload_byte_length < 56 {
-                return Err(Error::ShortListEncodedAsLong);
+                reject_non_canonical(Error::ShortListEncodedAsLong, data, lenient)?;
             }
 
             Ok((
@@ -124,6 +146,31 @@ pub fn decode_header_unchecked(
     }
 }
 
+/// Returns `Err(error)` for the non-canonical form `error` found in `data`,
+/// unless `lenient` is true and it is accepted inside `decode_lenient`.
+#[inline]
+pub(crate) fn reject_non_canonical(error: Error, data: &[u8], lenient: bool) -> Result<(), Error> {
+    #[cfg(feature = "std")]
+    if lenient && crate::lenient::accept(&error, data) {
+        return Ok(());
+    }
+    #[cfg(not(feature = "std"))]
+    let _ = (data, lenient);
+    Err(error)
+}
+
+/// Returns true inside `decode_lenient` decoding `data`.
+#[inline]
+pub(crate) fn is_lenient(data: &[u8]) -> bool {
+    #[cfg(feature = "std")]
+    return crate::lenient::is_lenient(data);
+    #[cfg(not(feature = "std"))]
+    {
+        let _ = data;
+        false
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use hex_literal::hex;
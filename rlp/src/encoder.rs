@@ -6,26 +6,161 @@
 
 //! Provides convenience functions for RLP encoding.
 
-use crate::Encode;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::{Encode, Error, Output};
 
 /// Encodes `value` and appends the result to `output`.
 #[inline]
-pub fn encode_to<T: Encode>(value: &T, output: &mut Vec<u8>) {
+pub fn encode_to<T: Encode, O: Output + ?Sized>(value: &T, output: &mut O) {
     <T as Encode>::encode_to(value, output);
 }
 
+/// Returns the length in bytes of the encoding of `value`.
+#[inline]
+pub fn encoded_len<T: Encode>(value: &T) -> usize {
+    <T as Encode>::encoded_len(value)
+}
+
 /// Encodes `value` and returns the result.
 #[inline]
 pub fn encode<T: Encode>(value: &T) -> Vec<u8> {
-    let mut output = vec![];
+    let mut output = Vec::with_capacity(encoded_len(value));
     <T as Encode>::encode_to(value, &mut output);
     output
 }
 
+/// Encodes `value` and writes the result to `writer`.
+///
+/// The encoding is written piece by piece as it is produced, without being
+/// collected in a buffer first, except for the lists of lists of `Vec<T>` and
+/// `&[T]`, which are buffered whole. Wrap `writer` in a `std::io::BufWriter`
+/// if small writes are costly, e.g. for a file or a socket.
+///
+/// # Examples
+///
+/// ```
+/// use rlp::encode_to_writer;
+///
+/// let mut writer = std::io::Cursor::new(vec![]);
+/// encode_to_writer(&65536_u32, &mut writer).unwrap();
+/// assert_eq!(writer.into_inner(), [0x83, 0x01, 0x00, 0x00]);
+/// ```
+#[cfg(feature = "std")]
+pub fn encode_to_writer<T: Encode, W: io::Write>(value: &T, writer: &mut W) -> io::Result<()> {
+    let mut output = WriterOutput {
+        writer,
+        result: Ok(()),
+    };
+    <T as Encode>::encode_to(value, &mut output);
+    output.result
+}
+
+/// Encodes `value` into the beginning of `output`, and returns the length in
+/// bytes of the encoding.
+///
+/// Returns `Err(Error::OutputBufferTooSmall)` if the encoding doesn't fit in
+/// `output`, which is left untouched unless `Encode::encoded_len` of `T`
+/// doesn't match its encoding.
+///
+/// # Examples
+///
+/// ```
+/// use rlp::{encode_to_slice, Error};
+///
+/// let mut buffer = [0; 8];
+/// let len = encode_to_slice(&65536_u32, &mut buffer).unwrap();
+/// assert_eq!(&buffer[..len], [0x83, 0x01, 0x00, 0x00]);
+///
+/// let mut buffer = [0; 2];
+/// assert_eq!(
+///     encode_to_slice(&65536_u32, &mut buffer).unwrap_err(),
+///     Error::OutputBufferTooSmall
+/// );
+/// ```
+pub fn encode_to_slice<T: Encode>(value: &T, output: &mut [u8]) -> Result<usize, Error> {
+    if encoded_len(value) > output.len() {
+        return Err(Error::OutputBufferTooSmall);
+    }
+
+    let mut slice_output = SliceOutput {
+        slice: output,
+        position: 0,
+        overflowed: false,
+    };
+    <T as Encode>::encode_to(value, &mut slice_output);
+    if slice_output.overflowed {
+        return Err(Error::OutputBufferTooSmall);
+    }
+    Ok(slice_output.position)
+}
+
+/// Adapts a `std::io::Write` to `Output`, keeping the first error.
+#[cfg(feature = "std")]
+struct WriterOutput<'a, W: io::Write> {
+    writer: &'a mut W,
+    result: io::Result<()>,
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write> Output for WriterOutput<'_, W> {
+    fn push(&mut self, byte: u8) {
+        self.extend_from_slice(&[byte]);
+    }
+
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        if self.result.is_ok() {
+            self.result = self.writer.write_all(bytes);
+        }
+    }
+}
+
+/// An `Output` which only counts the bytes, for the default
+/// `Encode::encoded_len`.
+pub(crate) struct LenOutput(pub(crate) usize);
+
+impl Output for LenOutput {
+    #[inline]
+    fn push(&mut self, _byte: u8) {
+        self.0 += 1;
+    }
+
+    #[inline]
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        self.0 += bytes.len();
+    }
+}
+
+/// Adapts a `&mut [u8]` to `Output`, recording whether the encoding
+/// overflowed it.
+struct SliceOutput<'a> {
+    slice: &'a mut [u8],
+    position: usize,
+    overflowed: bool,
+}
+
+impl Output for SliceOutput<'_> {
+    fn push(&mut self, byte: u8) {
+        self.extend_from_slice(&[byte]);
+    }
+
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        let end = self.position + bytes.len();
+        match self.slice.get_mut(self.position..end) {
+            Some(slice) if !self.overflowed => {
+                slice.copy_from_slice(bytes);
+                self.position = end;
+            }
+            _ => self.overflowed = true,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use hex_literal::hex;
-    use parity_rlp;
 
     use super::*;
 
@@ -77,4 +212,89 @@ mod tests {
         }
         assert_eq!(encoded, stream.out());
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_encode_to_writer() {
+        let data: Vec<Vec<u64>> = vec![(0..60).collect(), vec![], (0..3).collect()];
+
+        let mut writer = vec![];
+        encode_to_writer(&data, &mut writer).unwrap();
+        assert_eq!(writer, encode(&data));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_encode_to_writer_error() {
+        let mut buffer = [0; 2];
+        let mut writer = std::io::Cursor::new(&mut buffer[..]);
+
+        let err = encode_to_writer(&65536_u32, &mut writer).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+    }
+
+    #[test]
+    fn test_encode_to_slice() {
+        let data: Vec<Vec<u64>> = vec![(0..60).collect(), vec![], (0..3).collect()];
+        let encoded = encode(&data);
+
+        let mut buffer = vec![0xff; encoded.len() + 1];
+        assert_eq!(encode_to_slice(&data, &mut buffer).unwrap(), encoded.len());
+        assert_eq!(&buffer[..encoded.len()], encoded);
+        assert_eq!(buffer[encoded.len()], 0xff);
+
+        let mut buffer = vec![0xff; encoded.len() - 1];
+        assert_eq!(
+            encode_to_slice(&data, &mut buffer).unwrap_err(),
+            Error::OutputBufferTooSmall
+        );
+        assert!(buffer.iter().all(|&b| b == 0xff));
+    }
+
+    #[test]
+    fn test_encode_to_slice_inconsistent_encoded_len() {
+        struct Bytes(Vec<u8>, usize);
+
+        impl Encode for Bytes {
+            fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+                self.0.encode_to(output);
+            }
+
+            fn encoded_len(&self) -> usize {
+                self.1
+            }
+        }
+
+        let encoded = encode(&vec![0xab_u8; 3]);
+
+        // The actual length is returned.
+        let mut buffer = [0xff; 8];
+        let value = Bytes(vec![0xab; 3], 2);
+        assert_eq!(encode_to_slice(&value, &mut buffer).unwrap(), 4);
+        assert_eq!(buffer[..4], encoded);
+
+        // The encoding doesn't fit.
+        let mut buffer = [0xff; 3];
+        assert_eq!(
+            encode_to_slice(&value, &mut buffer).unwrap_err(),
+            Error::OutputBufferTooSmall
+        );
+    }
+
+    #[test]
+    fn test_default_encoded_len() {
+        struct Bytes(Vec<u8>);
+
+        impl Encode for Bytes {
+            fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+                self.0.encode_to(output);
+            }
+        }
+
+        for len in [0, 1, 55, 56, 1000] {
+            let value = Bytes(vec![0xff; len]);
+            assert_eq!(encoded_len(&value), encode(&value.0).len());
+            assert_eq!(encode(&value), encode(&value.0));
+        }
+    }
 }
@@ -6,59 +6,79 @@
 
 //! Implements RLP encoding.
 
+use alloc::vec::Vec;
+
 use extensions::strip_left_padding;
 
 use crate::constants::MAX_BYTE_LENGTH_OF_PAYLOAD_BYTE_LENGTH;
-use crate::{ItemPayloadSlice, ItemType};
+use crate::{ItemPayloadSlice, ItemType, Output};
 
 impl<'a> ItemPayloadSlice<'a> {
     /// Encodes `self` as a single value and appends the result to `output`.
     #[inline]
-    pub fn encode_as_single_value(self, output: &mut Vec<u8>) {
-        self.encode(ItemType::SingleValue, output);
+    pub fn encode_as_single_value<O: Output + ?Sized>(self, output: &mut O) {
+        if !self.is_single_byte_item() {
+            encode_header(ItemType::SingleValue, self.0.len(), output);
+        }
+        output.extend_from_slice(self.0);
     }
 
     /// Encodes `self` as a list and appends the result to `output`.
     #[inline]
-    pub fn encode_as_list(self, output: &mut Vec<u8>) {
-        self.encode(ItemType::List, output);
+    pub fn encode_as_list<O: Output + ?Sized>(self, output: &mut O) {
+        encode_header(ItemType::List, self.0.len(), output);
+        output.extend_from_slice(self.0);
     }
 
-    /// Encodes `self` as `item_type` and appends the result to `output`.
+    /// Returns the length in bytes of `self` encoded as a single value.
     #[inline]
-    fn encode(self, item_type: ItemType, output: &mut Vec<u8>) {
-        encode_payload_length(item_type, self, output);
-        output.extend(self.0);
+    pub fn encoded_len_as_single_value(self) -> usize {
+        if self.is_single_byte_item() {
+            return 1;
+        }
+        encoded_header_len(self.0.len()) + self.0.len()
+    }
+
+    /// Returns the length in bytes of `self` encoded as a list.
+    #[inline]
+    pub fn encoded_len_as_list(self) -> usize {
+        encoded_header_len(self.0.len()) + self.0.len()
     }
-}
 
-/// Encodes the length of `payload` according to `item_type` and appends the
-/// result to `output`.
-fn encode_payload_length(item_type: ItemType, payload: ItemPayloadSlice, output: &mut Vec<u8>) {
-    let payload_length = payload.0.len();
-
-    if item_type == ItemType::SingleValue
-        && payload_length == 1
-        && *payload.0.first().unwrap() < 0x80
-    {
-        // "For a single byte whose value is in the [0x00, 0x7f] range, that
-        // byte is its own RLP encoding."
-        return;
+    /// Returns true if `self` is a single byte in the [0x00, 0x7f] range.
+    ///
+    /// "For a single byte whose value is in the [0x00, 0x7f] range, that
+    /// byte is its own RLP encoding."
+    #[inline]
+    fn is_single_byte_item(self) -> bool {
+        matches!(self.0, [byte] if *byte < 0x80)
     }
+}
 
-    if payload_length < 56 {
+/// Encodes the header of an item of `item_type` with a payload of
+/// `payload_byte_length` bytes, and appends the result to `output`.
+///
+/// Allows a payload to be encoded right after its header, without being
+/// copied from a temporary buffer. Note that a single value of one byte in
+/// the [0x00, 0x7f] range has no header, which the caller has to handle.
+pub fn encode_header<O: Output + ?Sized>(
+    item_type: ItemType,
+    payload_byte_length: usize,
+    output: &mut O,
+) {
+    if payload_byte_length < 56 {
         match item_type {
             // "...if a string is 0-55 bytes long, the RLP encoding consists of
             // a single byte with value 0x80 plus the length of the string..."
             ItemType::SingleValue => {
-                output.push(0x80 + payload_length as u8);
+                output.push(0x80 + payload_byte_length as u8);
             }
 
             // "...if the total payload of a list is 0-55 bytes long, the RLP
             // encoding consists of a single byte with value 0xc0 plus the
             // length of the list..."
             ItemType::List => {
-                output.push(0xc0 + payload_length as u8);
+                output.push(0xc0 + payload_byte_length as u8);
             }
         }
     } else {
@@ -74,7 +94,7 @@ fn encode_payload_length(item_type: ItemType, payload: ItemPayloadSlice, output:
 
         // Represents `data_length` in bytes, big-endian without left padding
         // (leading zeroes)
-        let bytes = payload_length.to_be_bytes();
+        let bytes = payload_byte_length.to_be_bytes();
         let payload_length_bytes = strip_left_padding(&bytes);
         if payload_length_bytes.len() > MAX_BYTE_LENGTH_OF_PAYLOAD_BYTE_LENGTH {
             // Unlikely, for `usize` is normally up to a maximum of 8 bytes.
@@ -85,6 +105,67 @@ fn encode_payload_length(item_type: ItemType, payload: ItemPayloadSlice, output:
         // binary form..."
         output.push(base_value + payload_length_bytes.len() as u8);
         // "...followed by the length of the string/payload..."
-        output.extend(payload_length_bytes);
+        output.extend_from_slice(payload_length_bytes);
+    }
+}
+
+/// Encodes the header of the list whose payload follows the byte reserved
+/// for the header at `header_offset` in `output`.
+///
+/// The payload is moved to make room for a header longer than a byte, for a
+/// payload of more than 55 bytes.
+pub(crate) fn encode_reserved_list_header(output: &mut Vec<u8>, header_offset: usize) {
+    let payload_byte_length = output.len() - header_offset - 1;
+
+    if payload_byte_length < 56 {
+        output[header_offset] = 0xc0 + payload_byte_length as u8;
+    } else {
+        let mut header = Vec::new();
+        encode_header(ItemType::List, payload_byte_length, &mut header);
+        output.splice(header_offset..header_offset + 1, header);
+    }
+}
+
+/// Returns the length in bytes of the header encoded by `encode_header` for
+/// a payload of `payload_byte_length` bytes.
+#[inline]
+pub fn encoded_header_len(payload_byte_length: usize) -> usize {
+    if payload_byte_length < 56 {
+        1
+    } else {
+        1 + strip_left_padding(&payload_byte_length.to_be_bytes()).len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use super::*;
+
+    #[test]
+    fn test_encoded_len() {
+        let test_data = [
+            (&hex!("") as &[u8], 1, 1),
+            (&hex!("7f"), 1, 2),
+            (&hex!("80"), 2, 2),
+            (&[0; 55], 56, 56),
+            (&[0; 56], 58, 58),
+            (&[0; 256], 259, 259),
+        ];
+
+        for (payload, single_value_len, list_len) in test_data {
+            let payload = ItemPayloadSlice(payload);
+
+            let mut output = vec![];
+            payload.encode_as_single_value(&mut output);
+            assert_eq!(payload.encoded_len_as_single_value(), output.len());
+            assert_eq!(payload.encoded_len_as_single_value(), single_value_len);
+
+            let mut output = vec![];
+            payload.encode_as_list(&mut output);
+            assert_eq!(payload.encoded_len_as_list(), output.len());
+            assert_eq!(payload.encoded_len_as_list(), list_len);
+        }
     }
 }
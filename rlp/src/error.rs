@@ -4,10 +4,25 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-#[allow(missing_docs)]
+use alloc::boxed::Box;
+#[cfg(feature = "serde")]
+use alloc::string::{String, ToString};
+
+use crate::error_context::ErrorContext;
+use crate::{ItemDataSlice, Limit, PathSegment};
 
 /// Errors which can occur when performing RLP encoding and decoding.
-#[derive(thiserror::Error, Debug, PartialEq)]
+///
+/// More variants may be added, e.g. by enabling features, so matches need a
+/// wildcard arm.
+///
+/// A decoding error may carry the context where it occurred, as
+/// `Error::InContext`, see `with_error_context`. Errors compare equal
+/// regardless of their context, and are matched on with
+/// `Error::without_context`.
+#[allow(missing_docs)]
+#[derive(thiserror::Error, Clone, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("byte length of item data doesn't match header info")]
     ItemDataWithInvalidByteLength,
@@ -22,6 +37,29 @@ pub enum Error {
     #[error("unexpected decoding item type")]
     ItemTypeDoesNotMatch,
 
+    // encoder
+    #[error("output buffer is too small for the encoding")]
+    OutputBufferTooSmall,
+
+    // stream decoder
+    #[error("item byte length exceeds the maximum")]
+    MaximumItemByteLengthExceeded,
+    #[cfg(feature = "std")]
+    #[error("I/O error: {0}")]
+    Io(std::io::ErrorKind),
+
+    // decode options
+    #[error("decoding limit exceeded: {0:?}")]
+    LimitExceeded(Limit),
+
+    // view
+    #[error("list item index out of range")]
+    ListItemIndexOutOfRange,
+
+    // raw
+    #[error("raw item decoded without its header")]
+    RawItemWithoutHeader,
+
     // prelude
     #[error("byte length of item payload exceeds decoding type capacity")]
     ItemPayloadByteLengthTooLarge,
@@ -33,4 +71,100 @@ pub enum Error {
     ListDecodingNumberDoesNotMatch,
     #[error("cannot create a new value from its byte representation")]
     InvalidByteRepresentaion,
+    #[error("invalid UTF-8 string")]
+    InvalidUtf8String,
+    #[error("invalid boolean")]
+    InvalidBoolean,
+    #[error("keys not sorted or not unique")]
+    KeysNotSortedOrNotUnique,
+
+    // serde
+    #[cfg(feature = "serde")]
+    #[error("{0}")]
+    Serde(String),
+
+    // derive
+    #[error("unknown enum variant tag")]
+    UnknownEnumVariantTag,
+    #[error("no enum variant matches the decoding data")]
+    NoEnumVariantMatched,
+
+    // error context
+    #[error("{}", .0.error)]
+    InContext(Box<ErrorContext>),
+}
+
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.without_context(), other.without_context()) {
+            #[cfg(feature = "std")]
+            (Error::Io(a), Error::Io(b)) => a == b,
+            (Error::LimitExceeded(a), Error::LimitExceeded(b)) => a == b,
+            #[cfg(feature = "serde")]
+            (Error::Serde(a), Error::Serde(b)) => a == b,
+            // The other variants have no data.
+            (a, b) => core::mem::discriminant(a) == core::mem::discriminant(b),
+        }
+    }
+}
+
+impl Error {
+    /// Returns the error, recording that it occurred in the field `name` of
+    /// the type `type_name`, see `with_error_context`.
+    #[inline]
+    pub fn in_field(self, type_name: &'static str, name: &'static str) -> Self {
+        ErrorContext::extend(self, |context| {
+            context.record_segment(PathSegment::Field { type_name, name })
+        })
+    }
+
+    /// Returns the error, recording that it occurred in the item `item_data`,
+    /// see `with_error_context`.
+    ///
+    /// Only the innermost item is kept.
+    #[inline]
+    pub fn at_item(self, item_data: ItemDataSlice) -> Self {
+        ErrorContext::extend(self, |context| context.record_item(item_data.0))
+    }
+
+    /// Returns the error, recording that it occurred in the list item at
+    /// `index`, see `with_error_context`.
+    #[inline]
+    pub fn in_list_item(self, index: usize) -> Self {
+        ErrorContext::extend(self, |context| {
+            context.record_segment(PathSegment::Index(index))
+        })
+    }
+
+    /// Returns the error without the context it carries, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rlp::{decode, Error};
+    ///
+    /// // [1, []]
+    /// let err = decode::<Vec<u64>>(&[0xc2, 0x01, 0xc0]).unwrap_err();
+    /// assert!(matches!(err.without_context(), Error::ItemTypeDoesNotMatch));
+    /// ```
+    pub fn without_context(&self) -> &Error {
+        match self {
+            Error::InContext(context) => &context.error,
+            error => error,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::Error for Error {
+    fn custom<T: core::fmt::Display>(msg: T) -> Self {
+        Error::Serde(msg.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::de::Error for Error {
+    fn custom<T: core::fmt::Display>(msg: T) -> Self {
+        Error::Serde(msg.to_string())
+    }
 }
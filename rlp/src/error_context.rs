@@ -0,0 +1,264 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements the context of decoding errors, see `with_error_context`.
+//!
+//! The context is carried by the error, as `Error::InContext`, and is only
+//! built on the error path, so decoding stays as cheap as before otherwise.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{decode, Decode, Error};
+
+/// The context of an error, carried by `Error::InContext`.
+#[derive(Clone, Debug)]
+pub struct ErrorContext {
+    pub(crate) error: Error,
+    // The address in memory of the innermost item known to contain the error.
+    address: Option<usize>,
+    // The innermost segment first.
+    path: Vec<PathSegment>,
+}
+
+impl ErrorContext {
+    // Returns `error` in a context, extending the context of `error` if any.
+    #[cold]
+    #[inline(never)]
+    pub(crate) fn extend(error: Error, f: impl FnOnce(&mut ErrorContext)) -> Error {
+        let mut context = match error {
+            Error::InContext(context) => context,
+            error => Box::new(ErrorContext {
+                error,
+                address: None,
+                path: Vec::new(),
+            }),
+        };
+        f(&mut context);
+        Error::InContext(context)
+    }
+
+    // Records the address of the item `data`, if no inner item has been
+    // recorded.
+    pub(crate) fn record_item(&mut self, data: &[u8]) {
+        if self.address.is_none() {
+            self.address = Some(data.as_ptr() as usize);
+        }
+    }
+
+    // Records `segment` as the parent of the recorded segments.
+    pub(crate) fn record_segment(&mut self, segment: PathSegment) {
+        self.path.push(segment);
+    }
+}
+
+/// A segment of the path to the item where a decoding error occurred.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathSegment {
+    /// The field `name` of the type `type_name`.
+    Field {
+        /// The name of the type.
+        type_name: &'static str,
+        /// The name of the field, or its index for a tuple struct.
+        name: &'static str,
+    },
+    /// The item at an index of a list.
+    Index(usize),
+}
+
+/// An error with the context where it occurred, returned by
+/// `with_error_context`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodeError {
+    error: Error,
+    offset: usize,
+    path: Vec<PathSegment>,
+}
+
+impl DecodeError {
+    /// Returns the error.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// Returns the error, dropping the context.
+    pub fn into_error(self) -> Error {
+        self.error
+    }
+
+    /// Returns the offset in bytes into the input of the innermost item known
+    /// to contain the error, 0 if it is not known.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the path to the item where the error occurred, the outermost
+    /// segment first.
+    pub fn path(&self) -> &[PathSegment] {
+        &self.path
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at byte {}", self.error, self.offset)?;
+        if self.path.is_empty() {
+            return Ok(());
+        }
+
+        f.write_str(", in ")?;
+        for (i, segment) in self.path.iter().enumerate() {
+            match *segment {
+                // Only the outermost type is named, e.g. `Nodes.records[3]`.
+                PathSegment::Field { type_name, name } if i == 0 => {
+                    write!(f, "{type_name}.{name}")?
+                }
+                PathSegment::Field { name, .. } => write!(f, ".{name}")?,
+                PathSegment::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Runs `f` decoding `data`, and returns its error with the context where it
+/// occurred.
+///
+/// The offsets are recorded for the items located in `data`, the paths for
+/// the list items decoded by the prelude and the fields of the derived types.
+/// Hand-written decoding implementations can record more with
+/// `Error::at_item`, `Error::in_field` and `Error::in_list_item`.
+///
+/// # Examples
+///
+/// ```
+/// use rlp::{decode, with_error_context, Error, PathSegment};
+///
+/// // [[1], [2, 65536]]
+/// let data = [0xc8, 0xc1, 0x01, 0xc5, 0x02, 0x83, 0x01, 0x00, 0x00];
+/// let err = with_error_context(&data, || decode::<Vec<Vec<u16>>>(&data)).unwrap_err();
+///
+/// assert_eq!(err.error(), &Error::ItemPayloadByteLengthTooLarge);
+/// assert_eq!(err.offset(), 5);
+/// assert_eq!(err.path(), [PathSegment::Index(1), PathSegment::Index(1)]);
+/// assert_eq!(
+///     err.to_string(),
+///     "byte length of item payload exceeds decoding type capacity at byte 5, in [1][1]"
+/// );
+/// ```
+pub fn with_error_context<T>(
+    data: &[u8],
+    f: impl FnOnce() -> Result<T, Error>,
+) -> Result<T, DecodeError> {
+    f().map_err(|error| {
+        let Error::InContext(context) = error else {
+            return DecodeError {
+                error,
+                offset: 0,
+                path: Vec::new(),
+            };
+        };
+        let range = data.as_ptr_range();
+        let offset = match context.address {
+            Some(address) if (range.start as usize..=range.end as usize).contains(&address) => {
+                address - range.start as usize
+            }
+            _ => 0,
+        };
+        DecodeError {
+            error: context.error,
+            offset,
+            path: context.path.into_iter().rev().collect(),
+        }
+    })
+}
+
+/// Decodes `data` to a `T`, and returns the error with the context where it
+/// occurred.
+pub fn decode_with_context<'a, T: Decode<'a>>(data: &'a [u8]) -> Result<T, DecodeError> {
+    with_error_context(data, || decode(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use super::*;
+
+    #[test]
+    fn test_context_of_nested_lists() {
+        // [[1, 2], [3, [4]], "a"]
+        let data = hex!("c8c20102c303c10461");
+
+        let err = decode_with_context::<(Vec<u64>, Vec<u64>, String)>(&data).unwrap_err();
+        assert_eq!(err.error(), &Error::ItemTypeDoesNotMatch);
+        assert_eq!(err.offset(), 6);
+        assert_eq!(err.path(), [PathSegment::Index(1), PathSegment::Index(1)]);
+    }
+
+    #[test]
+    fn test_context_of_malformed_item() {
+        // [1, [<truncated>]]
+        let data = hex!("c301c183");
+
+        let err = decode_with_context::<(u16, Vec<u64>)>(&data).unwrap_err();
+        assert_eq!(err.error(), &Error::ItemDataWithInvalidByteLength);
+        assert_eq!(err.offset(), 3);
+        assert_eq!(err.path(), [PathSegment::Index(1), PathSegment::Index(0)]);
+    }
+
+    #[test]
+    fn test_context_of_top_level_item() {
+        let err = decode_with_context::<u64>(&hex!("8100")).unwrap_err();
+        assert_eq!(err.error(), &Error::SingleByteEncodedAsTwo);
+        assert_eq!(err.offset(), 0);
+        assert!(err.path().is_empty());
+        assert_eq!(err.to_string(), "single byte encoded as two at byte 0");
+    }
+
+    #[test]
+    fn test_context_in_error() {
+        // [[1], 65536]
+        let data = hex!("c6c10183010000");
+
+        // The context is carried by the error, which compares equal to the
+        // error without it.
+        let err = decode::<(Vec<u8>, u32)>(&data).unwrap_err();
+        assert_eq!(err, Error::ItemTypeDoesNotMatch);
+        assert!(matches!(err, Error::InContext(_)));
+        assert_eq!(err.without_context(), &Error::ItemTypeDoesNotMatch);
+        assert_eq!(err.to_string(), Error::ItemTypeDoesNotMatch.to_string());
+
+        // The context of an error outside the data is not known.
+        let err = with_error_context(&[], || Err::<(), _>(err)).unwrap_err();
+        assert_eq!(err.offset(), 0);
+        assert_eq!(err.path(), [PathSegment::Index(0)]);
+    }
+
+    #[test]
+    fn test_context_of_handled_error() {
+        // [[1], 65536]
+        let data = hex!("c6c10183010000");
+
+        // The first attempt fails in [0], the second one in [1].
+        let err = with_error_context(&data, || {
+            decode::<(Vec<u8>, u32)>(&data)
+                .map(|_| ())
+                .or_else(|_| decode::<(Vec<u64>, u16)>(&data).map(|_| ()))
+        })
+        .unwrap_err();
+        assert_eq!(err.error(), &Error::ItemPayloadByteLengthTooLarge);
+        assert_eq!(err.offset(), 3);
+        assert_eq!(err.path(), [PathSegment::Index(1)]);
+    }
+}
@@ -0,0 +1,157 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Encodes a `BTreeMap<K, V>` as the flat list `[k1, v1, k2, v2, ...]`.
+//!
+//! By default, a map is encoded as the list of its entries
+//! `[[k1, v1], [k2, v2], ...]`. This module is meant for the derive attribute
+//! `with`, to select the flat encoding of a field. As for the default
+//! encoding, the keys are sorted and must be sorted and unique when decoding.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//!
+//! use rlp::{decode, encode, Decode, Encode};
+//!
+//! #[derive(Encode, Decode, Debug, PartialEq)]
+//! struct Record {
+//!     seq: u64,
+//!     #[rlp(with = "rlp::flat_map")]
+//!     pairs: BTreeMap<String, u64>,
+//! }
+//!
+//! let record = Record {
+//!     seq: 1,
+//!     pairs: BTreeMap::from([("b".to_string(), 2), ("a".to_string(), 1)]),
+//! };
+//! let data = encode(&record);
+//!
+//! // [1, ["a", 1, "b", 2]]
+//! assert_eq!(data, [0xc6, 0x01, 0xc4, b'a', 0x01, b'b', 0x02]);
+//! assert_eq!(decode::<Record>(&data).unwrap(), record);
+//! ```
+
+use alloc::collections::BTreeMap;
+
+use crate::{
+    encode_header, encoded_header_len, Decode, Encode, Error, ItemDataSlice, ItemPayloadSlice,
+    ItemType, Output,
+};
+
+/// Encodes `map` as a flat list to `output`.
+pub fn encode_to<K: Encode, V: Encode, O: Output + ?Sized>(map: &BTreeMap<K, V>, output: &mut O) {
+    encode_header(ItemType::List, payload_len(map), output);
+    for (key, value) in map {
+        key.encode_to(output);
+        value.encode_to(output);
+    }
+}
+
+/// Returns the length in bytes of `map` encoded as a flat list.
+pub fn encoded_len<K: Encode, V: Encode>(map: &BTreeMap<K, V>) -> usize {
+    let payload_len = payload_len(map);
+    encoded_header_len(payload_len) + payload_len
+}
+
+/// Decodes a map from the item of a flat list.
+///
+/// Returns `Err(Error::KeysNotSortedOrNotUnique)` if the keys aren't
+/// strictly increasing.
+pub fn decode<'a, K, V>(
+    item_type: ItemType,
+    _item_data: ItemDataSlice<'a>,
+    payload: ItemPayloadSlice<'a>,
+) -> Result<BTreeMap<K, V>, Error>
+where
+    K: Decode<'a> + Ord,
+    V: Decode<'a>,
+{
+    if item_type != ItemType::List {
+        return Err(Error::ItemTypeDoesNotMatch);
+    }
+    let mut list_iter = payload.list_iter_unchecked();
+
+    let mut map = BTreeMap::new();
+    while !list_iter.remaining_list_payload().0.is_empty() {
+        let index = 2 * map.len();
+        let key: K = list_iter.next_item().map_err(|e| e.in_list_item(index))?;
+        if map.last_key_value().is_some_and(|(last, _)| *last >= key) {
+            return Err(Error::KeysNotSortedOrNotUnique.in_list_item(index));
+        }
+        let value: V = list_iter
+            .next_item()
+            .map_err(|e| e.in_list_item(index + 1))?;
+        map.insert(key, value);
+    }
+    Ok(map)
+}
+
+/// Returns the length in bytes of the flat list payload of `map`.
+fn payload_len<K: Encode, V: Encode>(map: &BTreeMap<K, V>) -> usize {
+    map.iter()
+        .map(|(key, value)| key.encoded_len() + value.encoded_len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use crate::decode_payload;
+
+    use super::*;
+
+    fn decode_flat_map(data: &[u8]) -> Result<BTreeMap<u64, &str>, Error> {
+        let (item_type, payload) = ItemDataSlice(data).as_payload()?;
+        decode(item_type, ItemDataSlice(data), payload)
+    }
+
+    #[test]
+    fn test_flat_map() {
+        let map = BTreeMap::from([(2_u64, "dog"), (1, "cat")]);
+        let mut output = vec![];
+        encode_to(&map, &mut output);
+
+        // [1, "cat", 2, "dog"]
+        assert_eq!(output, hex!("ca 01 83636174 02 83646f67"));
+        assert_eq!(encoded_len(&map), output.len());
+        assert_eq!(decode_flat_map(&output).unwrap(), map);
+        // The flat list isn't a list of entries.
+        assert_eq!(
+            decode_payload::<BTreeMap<u64, &str>>(ItemType::List, ItemPayloadSlice(&output[1..]))
+                .unwrap_err(),
+            Error::ItemTypeDoesNotMatch
+        );
+    }
+
+    #[test]
+    fn test_decoding_errors() {
+        let test_data = [
+            // [2, "dog", 1, "cat"]
+            (
+                &hex!("ca 02 83646f67 01 83636174") as &[u8],
+                Error::KeysNotSortedOrNotUnique,
+            ),
+            // [1, "cat", 1, "dog"]
+            (
+                &hex!("ca 01 83636174 01 83646f67"),
+                Error::KeysNotSortedOrNotUnique,
+            ),
+            // [1, "cat", 2]
+            (
+                &hex!("c6 01 83636174 02"),
+                Error::ListDecodingIterationEnded,
+            ),
+            (&hex!("01"), Error::ItemTypeDoesNotMatch),
+        ];
+
+        for (data, err) in test_data {
+            assert_eq!(decode_flat_map(data).unwrap_err(), err, "{data:x?}");
+        }
+    }
+}
@@ -0,0 +1,69 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements hashing of RLP encodings.
+
+use digest::Digest;
+
+use crate::{Encode, Output};
+
+/// Encodes `value` and returns the hash of the result computed by `D`.
+///
+/// The encoding is fed to the hasher piece by piece as it is produced,
+/// without being collected in a buffer first.
+///
+/// # Examples
+///
+/// ```
+/// use sha3::Keccak256;
+///
+/// // The hash of the empty list, e.g. the hash of the ommers of a block.
+/// let hash = rlp::hash::<Keccak256, _>(&Vec::<u64>::new());
+/// assert_eq!(hash[..4], [0x1d, 0xcc, 0x4d, 0xe8]);
+/// ```
+pub fn hash<D: Digest, T: Encode>(value: &T) -> digest::Output<D> {
+    let mut output = DigestOutput(D::new());
+    <T as Encode>::encode_to(value, &mut output);
+    output.0.finalize()
+}
+
+/// Adapts a `Digest` to `Output`.
+struct DigestOutput<D: Digest>(D);
+
+impl<D: Digest> Output for DigestOutput<D> {
+    fn push(&mut self, byte: u8) {
+        self.0.update([byte]);
+    }
+
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+    use sha3::Keccak256;
+
+    use crate::encode;
+
+    use super::*;
+
+    #[test]
+    fn test_hash() {
+        // The hash of the empty trie, the root of a trie without any node.
+        assert_eq!(
+            hash::<Keccak256, _>(&""),
+            hex!("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421").into()
+        );
+
+        let data: Vec<Vec<u64>> = vec![(0..60).collect(), vec![], (0..3).collect()];
+        assert_eq!(
+            hash::<Keccak256, _>(&data),
+            Keccak256::digest(encode(&data))
+        );
+    }
+}
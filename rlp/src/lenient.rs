@@ -0,0 +1,316 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements lenient decoding, see `decode_lenient`.
+//!
+//! The mode is looked up in a thread local only when a non-canonical form is
+//! found, so strict decoding stays as cheap as before.
+
+use std::cell::RefCell;
+
+use crate::{decode, Decode, Error};
+
+thread_local! {
+    static LENIENCY: RefCell<Option<Leniency>> = const { RefCell::new(None) };
+}
+
+// The non-canonical forms accepted so far for the input at `start..end` in
+// memory.
+struct Leniency {
+    start: usize,
+    end: usize,
+    warnings: Vec<Warning>,
+}
+
+/// A non-canonical form accepted by `decode_lenient`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Warning {
+    error: Error,
+    offset: usize,
+}
+
+impl Warning {
+    /// Returns the error strict decoding returns for the form, one of
+    /// `Error::SingleByteEncodedAsTwo`, `Error::ShortStringEncodedAsLong`,
+    /// `Error::ShortListEncodedAsLong` and
+    /// `Error::UintDecodingFoundLeftPadding`.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// Returns the offset in bytes into the input of the non-canonical header,
+    /// or of the payload of a left padded integer.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+/// Decodes `data` to a `T` in lenient mode, and returns it with the
+/// non-canonical forms accepted, in the order they were found.
+///
+/// Lenient mode accepts the following forms, which are rejected by default
+/// as required by consensus:
+///
+/// - a single byte in the [0x00, 0x7f] range encoded as a string of length 1
+/// - a string or a list of 0-55 bytes encoded with a long header
+/// - an unsigned integer left padded with zeroes
+///
+/// It is meant for the data of legacy encoders, the decoded values being
+/// encoded canonically. Only the items of `data` decoded by `T` are decoded
+/// leniently: `validate`, `decode_header_unchecked` and
+/// `ItemDataSlice::as_payload` stay strict, and so does the decoding of any
+/// other data.
+///
+/// # Examples
+///
+/// ```
+/// use rlp::{decode, decode_lenient, Error};
+///
+/// // [0x01 encoded as two bytes, 0x0400 left padded]
+/// let data = [0xc6, 0x81, 0x01, 0x83, 0x00, 0x04, 0x00];
+/// assert_eq!(decode::<Vec<u64>>(&data).unwrap_err(), Error::SingleByteEncodedAsTwo);
+///
+/// let (value, warnings) = decode_lenient::<Vec<u64>>(&data).unwrap();
+/// assert_eq!(value, [1, 0x0400]);
+/// assert_eq!(warnings[0].error(), &Error::SingleByteEncodedAsTwo);
+/// assert_eq!(warnings[0].offset(), 1);
+/// assert_eq!(warnings[1].error(), &Error::UintDecodingFoundLeftPadding);
+/// assert_eq!(warnings[1].offset(), 4);
+/// ```
+pub fn decode_lenient<'a, T: Decode<'a>>(data: &'a [u8]) -> Result<(T, Vec<Warning>), Error> {
+    let range = data.as_ptr_range();
+    let leniency = Leniency {
+        start: range.start as usize,
+        end: range.end as usize,
+        warnings: vec![],
+    };
+    let guard = LeniencyGuard {
+        outer: LENIENCY.with(|l| l.replace(Some(leniency))),
+    };
+    let result = decode(data);
+    let leniency = LENIENCY.with(|l| l.take()).expect("leniency is recorded");
+    drop(guard);
+
+    result.map(|value| (value, leniency.warnings))
+}
+
+// Restores the outer leniency when dropped, also if the decoding panics.
+struct LeniencyGuard {
+    outer: Option<Leniency>,
+}
+
+impl Drop for LeniencyGuard {
+    fn drop(&mut self) {
+        LENIENCY.with(|l| l.replace(self.outer.take()));
+    }
+}
+
+// Returns `true` inside `decode_lenient` decoding the input containing
+// `data`.
+#[cold]
+#[inline(never)]
+pub(crate) fn is_lenient(data: &[u8]) -> bool {
+    LENIENCY.with(|l| {
+        l.borrow()
+            .as_ref()
+            .is_some_and(|leniency| leniency.offset(data).is_some())
+    })
+}
+
+// Returns `true` inside `decode_lenient` decoding the input containing
+// `data`, recording the non-canonical form `error` found at `data`.
+#[cold]
+#[inline(never)]
+pub(crate) fn accept(error: &Error, data: &[u8]) -> bool {
+    LENIENCY.with(|l| {
+        let Some(ref mut leniency) = *l.borrow_mut() else {
+            return false;
+        };
+        let Some(offset) = leniency.offset(data) else {
+            return false;
+        };
+        leniency.warnings.push(Warning {
+            error: error.clone(),
+            offset,
+        });
+        true
+    })
+}
+
+impl Leniency {
+    // Returns the offset of `data` into the input, `None` if it is not a
+    // part of it.
+    fn offset(&self, data: &[u8]) -> Option<usize> {
+        let range = data.as_ptr_range();
+        let (start, end) = (range.start as usize, range.end as usize);
+        (self.start <= start && end <= self.end).then(|| start - self.start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use crate::{validate, ItemDataSlice, ItemPayloadSlice, ItemType, RlpRaw};
+
+    use super::*;
+
+    #[test]
+    fn test_non_canonical_forms() {
+        let test_data = [
+            (
+                &hex!("8101") as &[u8],
+                Error::SingleByteEncodedAsTwo,
+                hex!("01").to_vec(),
+            ),
+            // "dog" encoded as long
+            (
+                &hex!("b803646f67"),
+                Error::ShortStringEncodedAsLong,
+                b"dog".to_vec(),
+            ),
+        ];
+
+        for (data, error, value) in test_data {
+            assert_eq!(decode::<Vec<u8>>(data).unwrap_err(), error);
+
+            let (decoded, warnings) = decode_lenient::<Vec<u8>>(data).unwrap();
+            assert_eq!(decoded, value);
+            assert_eq!(warnings, [Warning { error, offset: 0 }]);
+        }
+
+        // [1, 2] encoded as long
+        let data = hex!("f8020102");
+        let (decoded, warnings) = decode_lenient::<Vec<u64>>(&data).unwrap();
+        assert_eq!(decoded, [1, 2]);
+        assert_eq!(warnings[0].error(), &Error::ShortListEncodedAsLong);
+    }
+
+    #[test]
+    fn test_left_padded_uints() {
+        // [0x0400 left padded, 0 encoded as 0x00]
+        let data = hex!("c5 83000400 00");
+        assert_eq!(
+            decode::<(u32, u64)>(&data).unwrap_err(),
+            Error::UintDecodingFoundLeftPadding
+        );
+        assert_eq!(
+            decode::<(u16, u64)>(&data).unwrap_err(),
+            Error::ItemPayloadByteLengthTooLarge
+        );
+
+        let (decoded, warnings) = decode_lenient::<(u16, u64)>(&data).unwrap();
+        assert_eq!(decoded, (0x0400, 0));
+        assert_eq!(
+            warnings,
+            [
+                Warning {
+                    error: Error::UintDecodingFoundLeftPadding,
+                    offset: 2,
+                },
+                Warning {
+                    error: Error::UintDecodingFoundLeftPadding,
+                    offset: 5,
+                }
+            ]
+        );
+
+        // The byte length is still checked without the left padding.
+        assert_eq!(
+            decode_lenient::<u16>(&hex!("83010000")).unwrap_err(),
+            Error::ItemPayloadByteLengthTooLarge
+        );
+    }
+
+    // Validates the data of its item while decoding it.
+    #[derive(Debug)]
+    struct Validated;
+
+    impl<'a> Decode<'a> for Validated {
+        const TYPE: ItemType = ItemType::List;
+
+        fn decode_item(
+            _item_type: ItemType,
+            item_data: ItemDataSlice<'a>,
+            _payload: ItemPayloadSlice<'a>,
+        ) -> Result<Self, Error> {
+            validate(item_data.0)?;
+            Ok(Validated)
+        }
+
+        fn decode(_payload: ItemPayloadSlice<'a>) -> Result<Self, Error> {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn test_strict_outside_decoded_items() {
+        // [0x01 encoded as two bytes, 2]
+        let data = hex!("c3 8101 02");
+        assert_eq!(
+            decode_lenient::<Validated>(&data).unwrap_err(),
+            Error::SingleByteEncodedAsTwo
+        );
+        assert_eq!(validate(&data).unwrap_err(), Error::SingleByteEncodedAsTwo);
+
+        // Other data decoded while decoding leniently.
+        #[derive(Debug)]
+        struct DecodingOther;
+
+        impl<'a> Decode<'a> for DecodingOther {
+            const TYPE: ItemType = ItemType::SingleValue;
+
+            fn decode(_payload: ItemPayloadSlice<'a>) -> Result<Self, Error> {
+                assert_eq!(
+                    decode::<u64>(&hex!("8102")).unwrap_err(),
+                    Error::SingleByteEncodedAsTwo
+                );
+                assert_eq!(
+                    ItemDataSlice(&hex!("8102")).as_payload().unwrap_err(),
+                    Error::SingleByteEncodedAsTwo
+                );
+                Ok(DecodingOther)
+            }
+        }
+
+        let (_, warnings) = decode_lenient::<DecodingOther>(&hex!("8101")).unwrap();
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_strict_after_panic() {
+        #[derive(Debug)]
+        struct Panicking;
+
+        impl<'a> Decode<'a> for Panicking {
+            const TYPE: ItemType = ItemType::SingleValue;
+
+            fn decode(_payload: ItemPayloadSlice<'a>) -> Result<Self, Error> {
+                panic!("decoding")
+            }
+        }
+
+        let data = hex!("8101");
+        let result = std::panic::catch_unwind(|| decode_lenient::<Panicking>(&data));
+        assert!(result.is_err());
+        assert!(LENIENCY.with(|l| l.borrow().is_none()));
+    }
+
+    #[test]
+    fn test_raw_items() {
+        // [0x01 encoded as two bytes, [2]]
+        let data = hex!("c4 8101 c102");
+        let (items, warnings) = decode_lenient::<Vec<RlpRaw>>(&data).unwrap();
+        assert_eq!(warnings.len(), 1);
+
+        assert_eq!(items[0].data().0, hex!("8101"));
+        assert_eq!(items[0].item_type(), ItemType::SingleValue);
+        assert_eq!(items[0].payload().0, hex!("01"));
+        assert_eq!(items[0].decode::<u64>().unwrap(), 1);
+        assert_eq!(items[0].view().as_bytes().unwrap(), hex!("01"));
+        assert_eq!(items[1].view().decode_at::<u64>(0).unwrap(), 2);
+    }
+}
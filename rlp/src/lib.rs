@@ -14,27 +14,81 @@
 //! [1]: https://ethereum.github.io/yellowpaper/paper.pdf
 //! [2]: https://ethereum.org/en/developers/docs/data-structures-and-encoding/rlp/
 //! [3]: https://arxiv.org/abs/2009.13769
+//!
+//! # Features
+//!
+//! - `std` (default): implements RLP for the IP addresses of `std::net`, and
+//!   enables the I/O of `encode_to_writer` and `StreamDecoder`, and the
+//!   lenient decoding of `decode_lenient`. Without it, the crate is
+//!   `no_std` and only requires `alloc`.
+//! - `serde`: serializes and deserializes `serde` types with `to_vec` and
+//!   `from_slice`.
+//! - `digest`: hashes encodings with `hash`.
+//! - `num-bigint`: implements RLP for `num_bigint::BigUint`.
+//! - `primitive-types`: implements RLP for `primitive_types::{U256, H160,
+//!   H256}`.
+//! - `ruint`: implements RLP for `ruint::Uint<BITS, LIMBS>`.
+//! - `bytes`: implements RLP for `bytes::Bytes`.
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 
+extern crate alloc;
+
 pub(crate) mod constants;
+#[cfg(feature = "serde")]
+mod de;
+mod decode_options;
 mod decoder;
 mod decoding;
 mod encoder;
 mod encoding;
 mod error;
+mod error_context;
+pub mod flat_map;
+#[cfg(feature = "digest")]
+mod hash;
+#[cfg(feature = "std")]
+mod lenient;
 mod list_iter;
 mod prelude;
+mod raw;
+#[cfg(feature = "serde")]
+mod ser;
+mod stream;
+#[cfg(feature = "std")]
+mod stream_decoder;
 mod traits;
 mod types;
+mod validate;
+mod view;
 
-pub use decoder::{decode, decode_payload};
+#[cfg(feature = "serde")]
+pub use de::from_slice;
+pub use decode_options::{DecodeOptions, Limit, Limits, DEFAULT_MAX_ITEM_BYTE_LENGTH};
+pub use decoder::{decode, decode_payload, decode_with_options};
 pub use decoding::decode_header_unchecked;
-pub use encoder::{encode, encode_to};
+#[cfg(feature = "std")]
+pub use encoder::encode_to_writer;
+pub use encoder::{encode, encode_to, encode_to_slice, encoded_len};
+pub use encoding::{encode_header, encoded_header_len};
 pub use error::Error;
+pub use error_context::{decode_with_context, with_error_context, DecodeError, PathSegment};
+#[cfg(feature = "digest")]
+pub use hash::hash;
+#[cfg(feature = "std")]
+pub use lenient::{decode_lenient, Warning};
 pub use list_iter::ListIter;
 pub use prelude::U8;
-pub use traits::{Decode, Encode};
+pub use raw::RlpRaw;
+#[cfg(feature = "serde")]
+pub use ser::to_vec;
+pub use stream::RlpStream;
+#[cfg(feature = "std")]
+pub use stream_decoder::{StreamDecoder, StreamItem};
+pub use traits::{Decode, DecodeOwned, Encode, Output};
 pub use types::{
     ByteLengthOfPayloadByteLength, ItemDataSlice, ItemPayloadSlice, ItemType, PayloadByteLength,
 };
+pub use validate::{validate, Stats};
+pub use view::RlpView;
@@ -4,20 +4,25 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use crate::decoding::decode_header;
 use crate::types::{HeaderByteLength, ItemDataSlice, ItemPayloadSlice};
-use crate::{decode_header_unchecked, decode_payload, Decode, Error, ItemType};
+use crate::{Decode, Error, ItemType};
 
 /// An iterator over the items of a RLP list.
+///
+/// Inside `decode_lenient`, it accepts the non-canonical headers of the data
+/// being decoded.
 #[derive(Debug)]
 pub struct ListIter<'a> {
     remaining_list_payload: ItemPayloadSlice<'a>,
+    lenient: bool,
 }
 
 impl<'a> ListIter<'a> {
     /// Decodes `item_data` and returns a `ListIter` wrapping the underlying
     /// payload of the list items.
     pub fn from_item_data(item_data: ItemDataSlice<'a>) -> Result<ListIter<'a>, Error> {
-        let (item_type, payload) = item_data.as_payload()?;
+        let (item_type, payload) = item_data.as_payload_with(true)?;
         if item_type != ItemType::List {
             return Err(Error::ItemTypeDoesNotMatch);
         }
@@ -31,6 +36,16 @@ impl<'a> ListIter<'a> {
     pub fn from_list_payload_unchecked(list_payload: ItemPayloadSlice<'a>) -> ListIter<'a> {
         ListIter {
             remaining_list_payload: list_payload,
+            lenient: true,
+        }
+    }
+
+    // Like `from_list_payload_unchecked`, rejecting the non-canonical headers
+    // even inside `decode_lenient`.
+    pub(crate) fn from_list_payload_strict(list_payload: ItemPayloadSlice<'a>) -> ListIter<'a> {
+        ListIter {
+            remaining_list_payload: list_payload,
+            lenient: false,
         }
     }
 
@@ -62,11 +77,24 @@ impl<'a> ListIter<'a> {
     /// Returns `Err(Error::ListDecodingIterationEnded)` when iteration is
     /// finished.
     pub fn next_item<T: Decode<'a>>(&mut self) -> Result<T, Error> {
-        match self.next() {
-            None => Err(Error::ListDecodingIterationEnded),
-            Some(Ok((item_type, payload))) => decode_payload(item_type, payload),
-            Some(Err(e)) => Err(e),
-        }
+        self.next_item_with(T::decode_item)
+            .unwrap_or(Err(Error::ListDecodingIterationEnded))
+    }
+
+    /// Advances the iterator and returns the next RLP item decoded with
+    /// `decode`, which is called like `Decode::decode_item`, or error if
+    /// decoding fails.
+    ///
+    /// Returns None when iteration is finished.
+    pub fn next_item_with<T>(
+        &mut self,
+        decode: impl FnOnce(ItemType, ItemDataSlice<'a>, ItemPayloadSlice<'a>) -> Result<T, Error>,
+    ) -> Option<Result<T, Error>> {
+        self.next_itemdata().map(|item| {
+            let (item_type, header_byte_length, item_data) = item?;
+            let payload = ItemPayloadSlice(&item_data.0[header_byte_length as usize..]);
+            decode(item_type, item_data, payload).map_err(|e| e.at_item(item_data))
+        })
     }
 
     /// Advances the iterator and returns the next item data pair.
@@ -79,12 +107,13 @@ impl<'a> ListIter<'a> {
             return None;
         }
 
-        match decode_header_unchecked(self.remaining_list_payload.0) {
+        match decode_header(self.remaining_list_payload.0, self.lenient) {
             Ok((item_type, header_byte_length, payload_byte_length)) => {
                 if (self.remaining_list_payload.0.len() - header_byte_length as usize)
                     < payload_byte_length as usize
                 {
-                    return Some(Err(Error::ItemDataWithInvalidByteLength));
+                    return Some(Err(Error::ItemDataWithInvalidByteLength
+                        .at_item(ItemDataSlice(self.remaining_list_payload.0))));
                 }
 
                 // TODO: handle overflow of
@@ -96,7 +125,7 @@ impl<'a> ListIter<'a> {
                 self.remaining_list_payload = ItemPayloadSlice(data2);
                 Some(Ok((item_type, header_byte_length, ItemDataSlice(data1))))
             }
-            Err(e) => Some(Err(e)),
+            Err(e) => Some(Err(e.at_item(ItemDataSlice(self.remaining_list_payload.0)))),
         }
     }
 }
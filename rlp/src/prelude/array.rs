@@ -0,0 +1,100 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements RLP for `[T; N]`, encoded as a list of `N` items.
+//!
+//! `[u8; N]` is implemented in `byte_array.rs` as a single value.
+
+use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType, Output};
+
+impl<'a, T, const N: usize> Decode<'a> for [T; N]
+where
+    T: Decode<'a>,
+{
+    const TYPE: ItemType = ItemType::List;
+
+    fn decode(payload: ItemPayloadSlice<'a>) -> Result<Self, Error> {
+        let mut list_iter = payload.list_iter_unchecked();
+
+        // Fails as soon as the number of items doesn't match, without
+        // decoding the extra items.
+        let mut items: [Option<T>; N] = core::array::from_fn(|_| None);
+        for (index, item) in items.iter_mut().enumerate() {
+            if list_iter.remaining_list_payload().0.is_empty() {
+                return Err(Error::ListDecodingNumberDoesNotMatch);
+            }
+            *item = Some(list_iter.next_item().map_err(|e| e.in_list_item(index))?);
+        }
+        if !list_iter.remaining_list_payload().0.is_empty() {
+            return Err(Error::ListDecodingNumberDoesNotMatch);
+        }
+        Ok(items.map(|item| item.expect("all the items are decoded")))
+    }
+}
+
+impl<T: Encode, const N: usize> Encode for [T; N] {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+        <&[T] as Encode>::encode_to(&self.as_slice(), output);
+    }
+
+    fn encoded_len(&self) -> usize {
+        <&[T] as Encode>::encoded_len(&self.as_slice())
+    }
+
+    fn encoded_type() -> ItemType {
+        ItemType::List
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use crate::{decode, encode, Error};
+
+    #[test]
+    fn test_array_of_u64() {
+        let data: [u64; 4] = [1, 2, 3, 65536];
+        let encoded = hex!("c701020383010000");
+
+        let output = encode(&data);
+        assert_eq!(output, encoded);
+
+        assert_eq!(decode::<[u64; 4]>(&output).unwrap(), data);
+    }
+
+    #[test]
+    fn test_array_of_byte_array() {
+        let data: [[u8; 2]; 2] = [[1, 2], [3, 4]];
+        let encoded = hex!("c6820102820304");
+
+        let output = encode(&data);
+        assert_eq!(output, encoded);
+
+        assert_eq!(decode::<[[u8; 2]; 2]>(&output).unwrap(), data);
+    }
+
+    #[test]
+    fn test_decoding_array_number_does_not_match() {
+        // py_playground: `encode_vec_of_uint_1_2_3`
+        let encoded = hex!("c3010203");
+
+        assert_eq!(
+            decode::<[u16; 2]>(&encoded).unwrap_err(),
+            Error::ListDecodingNumberDoesNotMatch
+        );
+        assert_eq!(
+            decode::<[u16; 4]>(&encoded).unwrap_err(),
+            Error::ListDecodingNumberDoesNotMatch
+        );
+
+        // The extra item isn't decoded, nor even valid.
+        assert_eq!(
+            decode::<[u16; 2]>(&hex!("c40102b8ff")).unwrap_err(),
+            Error::ListDecodingNumberDoesNotMatch
+        );
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements RLP for `num_bigint::BigUint`.
+
+use num_bigint::BigUint;
+
+use super::uint::{encode_uint_be_bytes, encoded_uint_len, uint_be_bytes};
+use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType, Output};
+
+impl Decode<'_> for BigUint {
+    const TYPE: ItemType = ItemType::SingleValue;
+
+    fn decode(payload: ItemPayloadSlice) -> Result<Self, Error> {
+        uint_be_bytes(payload, usize::MAX).map(BigUint::from_bytes_be)
+    }
+}
+
+impl Encode for BigUint {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+        encode_uint_be_bytes(&self.to_bytes_be(), output);
+    }
+
+    fn encoded_len(&self) -> usize {
+        encoded_uint_len(self.bits() as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use crate::{decode, encode, encoded_len};
+
+    use super::*;
+
+    #[test]
+    fn test_biguint() {
+        let test_data = [
+            // eth_rlp.py: `encode_uint_0`
+            (BigUint::ZERO, &hex!("80") as &[u8]),
+            // eth_rlp.py: `encode_uint_1`
+            (BigUint::from(1_u8), &hex!("01")),
+            // eth_rlp.py: `encode_uint_65536`
+            (BigUint::from(65536_u32), &hex!("83010000")),
+            // 2^512, with a long header
+            (
+                BigUint::from(1_u8) << 512,
+                &hex!("b841 01 00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"),
+            ),
+        ];
+
+        for (n, encoded) in test_data {
+            let output = encode(&n);
+            assert_eq!(output, encoded, "{n}");
+            assert_eq!(encoded_len(&n), output.len(), "{n}");
+            assert_eq!(decode::<BigUint>(&output).unwrap(), n, "{n}");
+        }
+    }
+
+    #[test]
+    fn test_decoding_left_padded() {
+        // eth_rlp.py: `encode_left_padded_bytes`
+        assert_eq!(
+            decode::<BigUint>(&hex!("820001")).unwrap_err(),
+            Error::UintDecodingFoundLeftPadding
+        );
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements RLP for `bool`.
+//!
+//! `false` is encoded as the integer 0 (`0x80`), and `true` as the integer 1
+//! (`0x01`). Other values are rejected when decoding.
+
+use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType, Output};
+
+impl Decode<'_> for bool {
+    const TYPE: ItemType = ItemType::SingleValue;
+
+    fn decode(payload: ItemPayloadSlice) -> Result<Self, Error> {
+        match payload.0 {
+            [] => Ok(false),
+            [1] => Ok(true),
+            _ => Err(Error::InvalidBoolean),
+        }
+    }
+}
+
+impl Encode for bool {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+        let payload: &[u8] = if *self { &[1] } else { &[] };
+        ItemPayloadSlice(payload).encode_as_single_value(output);
+    }
+
+    fn encoded_len(&self) -> usize {
+        // Either `0x01` or `0x80`
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use crate::{decode, encode, Error};
+
+    #[test]
+    fn test_bool() {
+        let test_data = [(false, &hex!("80") as &[u8]), (true, &hex!("01"))];
+
+        for (value, encoded) in test_data {
+            let output = encode(&value);
+            assert_eq!(output, encoded);
+
+            assert_eq!(decode::<bool>(&output).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_bool_decoding_errors() {
+        let test_data = [
+            // the byte 0x00, non-canonical integer 0
+            &hex!("00") as &[u8],
+            &hex!("02"),
+            &hex!("820001"),
+        ];
+
+        for data in test_data {
+            assert_eq!(decode::<bool>(data).unwrap_err(), Error::InvalidBoolean);
+        }
+        assert_eq!(
+            decode::<bool>(&hex!("c0")).unwrap_err(),
+            Error::ItemTypeDoesNotMatch
+        );
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements RLP for `BTreeMap<K, V>`.
+//!
+//! A map is encoded as the list of its entries `[key, value]` sorted by key,
+//! e.g. `[[k1, v1], [k2, v2]]`. When decoding, the keys must be sorted and
+//! unique, so a map has a single encoding. See `rlp::flat_map` to encode the
+//! keys and values in a single list.
+
+use alloc::collections::BTreeMap;
+
+use crate::{
+    encode_header, encode_to, encoded_header_len, encoded_len, Decode, Encode, Error,
+    ItemPayloadSlice, ItemType, Output,
+};
+
+impl<'a, K, V> Decode<'a> for BTreeMap<K, V>
+where
+    K: Decode<'a> + Ord,
+    V: Decode<'a>,
+{
+    const TYPE: ItemType = ItemType::List;
+
+    fn decode(payload: ItemPayloadSlice<'a>) -> Result<Self, Error> {
+        let mut list_iter = payload.list_iter_unchecked();
+
+        let mut map = BTreeMap::new();
+        while !list_iter.remaining_list_payload().0.is_empty() {
+            let index = map.len();
+            let (key, value): (K, V) = list_iter.next_item().map_err(|e| e.in_list_item(index))?;
+            insert_sorted(&mut map, key, value).map_err(|e| e.in_list_item(index))?;
+        }
+        Ok(map)
+    }
+}
+
+impl<K: Encode, V: Encode> Encode for BTreeMap<K, V> {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+        encode_header(ItemType::List, payload_len(self), output);
+        for (key, value) in self {
+            encode_header(
+                ItemType::List,
+                encoded_len(key) + encoded_len(value),
+                output,
+            );
+            encode_to(key, output);
+            encode_to(value, output);
+        }
+    }
+
+    fn encoded_len(&self) -> usize {
+        let payload_len = payload_len(self);
+        encoded_header_len(payload_len) + payload_len
+    }
+
+    fn encoded_type() -> ItemType {
+        ItemType::List
+    }
+}
+
+/// Returns the length in bytes of the list payload of `map`.
+fn payload_len<K: Encode, V: Encode>(map: &BTreeMap<K, V>) -> usize {
+    map.iter()
+        .map(|(key, value)| {
+            let entry_payload_len = encoded_len(key) + encoded_len(value);
+            encoded_header_len(entry_payload_len) + entry_payload_len
+        })
+        .sum()
+}
+
+/// Inserts `key` and `value` into `map`, as the greatest key.
+///
+/// Returns `Err(Error::KeysNotSortedOrNotUnique)` if `key` is not greater
+/// than the keys of `map`.
+fn insert_sorted<K: Ord, V>(map: &mut BTreeMap<K, V>, key: K, value: V) -> Result<(), Error> {
+    if map.last_key_value().is_some_and(|(last, _)| *last >= key) {
+        return Err(Error::KeysNotSortedOrNotUnique);
+    }
+    map.insert(key, value);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use crate::{decode, encode, encoded_len, Error, PathSegment};
+
+    use super::*;
+
+    #[test]
+    fn test_btree_map() {
+        let map = BTreeMap::from([(2_u64, "dog"), (1, "cat")]);
+        let output = encode(&map);
+
+        // [[1, "cat"], [2, "dog"]]
+        assert_eq!(output, hex!("ccc50183636174c50283646f67"));
+        assert_eq!(encoded_len(&map), output.len());
+        assert_eq!(decode::<BTreeMap<u64, &str>>(&output).unwrap(), map);
+
+        let empty: BTreeMap<u64, u64> = BTreeMap::new();
+        assert_eq!(encode(&empty), hex!("c0"));
+        assert_eq!(decode::<BTreeMap<u64, u64>>(&hex!("c0")).unwrap(), empty);
+    }
+
+    #[test]
+    fn test_decoding_errors() {
+        let test_data = [
+            // [[2, "dog"], [1, "cat"]]
+            (
+                &hex!("ccc50283646f67c50183636174") as &[u8],
+                Error::KeysNotSortedOrNotUnique,
+            ),
+            // [[1, "cat"], [1, "dog"]]
+            (
+                &hex!("ccc50183636174c50183646f67"),
+                Error::KeysNotSortedOrNotUnique,
+            ),
+            // [[1, "cat", 2]]
+            (
+                &hex!("c7c6018363617402"),
+                Error::ListDecodingNumberDoesNotMatch,
+            ),
+            // [1]
+            (&hex!("c101"), Error::ItemTypeDoesNotMatch),
+        ];
+
+        for (data, err) in test_data {
+            assert_eq!(
+                decode::<BTreeMap<u64, &str>>(data).unwrap_err(),
+                err,
+                "{data:x?}"
+            );
+        }
+
+        let err =
+            crate::decode_with_context::<BTreeMap<u64, &str>>(&hex!("ccc50283646f67c50183636174"))
+                .unwrap_err();
+        assert_eq!(err.path(), [PathSegment::Index(1)]);
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements RLP for `BTreeSet<T>`.
+//!
+//! A set is encoded as the list of its items sorted. When decoding, the items
+//! must be sorted and unique, so a set has a single encoding.
+
+use alloc::collections::BTreeSet;
+
+use crate::{
+    encode_header, encode_to, encoded_header_len, encoded_len, Decode, Encode, Error,
+    ItemPayloadSlice, ItemType, Output,
+};
+
+impl<'a, T> Decode<'a> for BTreeSet<T>
+where
+    T: Decode<'a> + Ord,
+{
+    const TYPE: ItemType = ItemType::List;
+
+    fn decode(payload: ItemPayloadSlice<'a>) -> Result<Self, Error> {
+        let mut list_iter = payload.list_iter_unchecked();
+
+        let mut set = BTreeSet::new();
+        while !list_iter.remaining_list_payload().0.is_empty() {
+            let index = set.len();
+            let item: T = list_iter.next_item().map_err(|e| e.in_list_item(index))?;
+            if set.last().is_some_and(|last| *last >= item) {
+                return Err(Error::KeysNotSortedOrNotUnique.in_list_item(index));
+            }
+            set.insert(item);
+        }
+        Ok(set)
+    }
+}
+
+impl<T: Encode> Encode for BTreeSet<T> {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+        encode_header(ItemType::List, payload_len(self), output);
+        self.iter().for_each(|item| {
+            encode_to(item, output);
+        });
+    }
+
+    fn encoded_len(&self) -> usize {
+        let payload_len = payload_len(self);
+        encoded_header_len(payload_len) + payload_len
+    }
+
+    fn encoded_type() -> ItemType {
+        ItemType::List
+    }
+}
+
+/// Returns the length in bytes of the list payload of `set`.
+fn payload_len<T: Encode>(set: &BTreeSet<T>) -> usize {
+    set.iter().map(encoded_len).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use crate::{decode, encode, encoded_len, Error};
+
+    use super::*;
+
+    #[test]
+    fn test_btree_set() {
+        let set = BTreeSet::from([3_u64, 1, 2]);
+        let output = encode(&set);
+
+        assert_eq!(output, hex!("c3010203"));
+        assert_eq!(encoded_len(&set), output.len());
+        assert_eq!(decode::<BTreeSet<u64>>(&output).unwrap(), set);
+    }
+
+    #[test]
+    fn test_decoding_errors() {
+        let test_data = [
+            &hex!("c3010302") as &[u8],
+            &hex!("c3010202"),
+            &hex!("c20101"),
+        ];
+
+        for data in test_data {
+            assert_eq!(
+                decode::<BTreeSet<u64>>(data).unwrap_err(),
+                Error::KeysNotSortedOrNotUnique,
+                "{data:x?}"
+            );
+        }
+    }
+}
@@ -6,7 +6,7 @@
 
 //! Implements RLP for `[u8; N]`.
 
-use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType};
+use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType, Output};
 
 impl<'a, const N: usize> Decode<'a> for [u8; N] {
     const TYPE: ItemType = ItemType::SingleValue;
@@ -20,9 +20,13 @@ impl<'a, const N: usize> Decode<'a> for [u8; N] {
 }
 
 impl<const N: usize> Encode for [u8; N] {
-    fn encode_to(&self, output: &mut Vec<u8>) {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
         ItemPayloadSlice(self).encode_as_single_value(output);
     }
+
+    fn encoded_len(&self) -> usize {
+        ItemPayloadSlice(self).encoded_len_as_single_value()
+    }
 }
 
 #[cfg(test)]
@@ -6,7 +6,7 @@
 
 //! Implements RLP for `&[u8]`.
 
-use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType};
+use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType, Output};
 
 impl<'a> Decode<'a> for &'a [u8] {
     const TYPE: ItemType = ItemType::SingleValue;
@@ -17,9 +17,13 @@ impl<'a> Decode<'a> for &'a [u8] {
 }
 
 impl Encode for &[u8] {
-    fn encode_to(&self, output: &mut Vec<u8>) {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
         ItemPayloadSlice(self).encode_as_single_value(output);
     }
+
+    fn encoded_len(&self) -> usize {
+        ItemPayloadSlice(self).encoded_len_as_single_value()
+    }
 }
 
 #[cfg(test)]
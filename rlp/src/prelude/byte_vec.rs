@@ -6,7 +6,9 @@
 
 //! Implements RLP for `Vec<u8>`.
 
-use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType};
+use alloc::vec::Vec;
+
+use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType, Output};
 
 impl<'a> Decode<'a> for Vec<u8> {
     const TYPE: ItemType = ItemType::SingleValue;
@@ -17,9 +19,13 @@ impl<'a> Decode<'a> for Vec<u8> {
 }
 
 impl Encode for Vec<u8> {
-    fn encode_to(&self, output: &mut Vec<u8>) {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
         ItemPayloadSlice(self.as_slice()).encode_as_single_value(output);
     }
+
+    fn encoded_len(&self) -> usize {
+        ItemPayloadSlice(self.as_slice()).encoded_len_as_single_value()
+    }
 }
 
 #[cfg(test)]
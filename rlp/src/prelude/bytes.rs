@@ -0,0 +1,47 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements RLP for `bytes::Bytes`.
+
+use bytes::Bytes;
+
+use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType, Output};
+
+impl<'a> Decode<'a> for Bytes {
+    const TYPE: ItemType = ItemType::SingleValue;
+
+    fn decode(payload: ItemPayloadSlice<'a>) -> Result<Self, Error> {
+        Ok(Bytes::copy_from_slice(payload.0))
+    }
+}
+
+impl Encode for Bytes {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+        ItemPayloadSlice(self).encode_as_single_value(output);
+    }
+
+    fn encoded_len(&self) -> usize {
+        ItemPayloadSlice(self).encoded_len_as_single_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{decode, encode};
+
+    use super::*;
+
+    #[test]
+    fn test_bytes() {
+        let data = Bytes::from_static(&[1, 2, 3]);
+        // eth_rlp.py: `encode_bytes_1_2_3`
+        let encoded = &[0x83, 1, 2, 3];
+
+        let output = encode(&data);
+        assert_eq!(output, encoded);
+        assert_eq!(decode::<Bytes>(&output).unwrap(), data);
+    }
+}
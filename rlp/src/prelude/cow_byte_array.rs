@@ -6,9 +6,9 @@
 
 //! Implements RLP for `Cow<[u8; N]>`.
 
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
-use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType};
+use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType, Output};
 
 impl<'a, const N: usize> Decode<'a> for Cow<'a, [u8; N]> {
     const TYPE: ItemType = ItemType::SingleValue;
@@ -23,9 +23,13 @@ impl<'a, const N: usize> Decode<'a> for Cow<'a, [u8; N]> {
 }
 
 impl<'a, const N: usize> Encode for Cow<'a, [u8; N]> {
-    fn encode_to(&self, output: &mut Vec<u8>) {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
         ItemPayloadSlice(self.as_slice()).encode_as_single_value(output);
     }
+
+    fn encoded_len(&self) -> usize {
+        ItemPayloadSlice(self.as_slice()).encoded_len_as_single_value()
+    }
 }
 
 #[cfg(test)]
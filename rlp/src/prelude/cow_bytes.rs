@@ -6,9 +6,9 @@
 
 //! Implements RLP for `Cow<[u8]>`.
 
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
-use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType};
+use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType, Output};
 
 impl<'a> Decode<'a> for Cow<'a, [u8]> {
     const TYPE: ItemType = ItemType::SingleValue;
@@ -19,9 +19,13 @@ impl<'a> Decode<'a> for Cow<'a, [u8]> {
 }
 
 impl<'a> Encode for Cow<'a, [u8]> {
-    fn encode_to(&self, output: &mut Vec<u8>) {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
         ItemPayloadSlice(self).encode_as_single_value(output);
     }
+
+    fn encoded_len(&self) -> usize {
+        ItemPayloadSlice(self).encoded_len_as_single_value()
+    }
 }
 
 #[cfg(test)]
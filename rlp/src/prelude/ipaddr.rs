@@ -6,7 +6,7 @@
 
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
-use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType};
+use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType, Output};
 
 impl Decode<'_> for IpAddr {
     const TYPE: ItemType = ItemType::SingleValue;
@@ -21,12 +21,19 @@ impl Decode<'_> for IpAddr {
 }
 
 impl Encode for IpAddr {
-    fn encode_to(&self, output: &mut Vec<u8>) {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
         match self {
             IpAddr::V4(ip) => <Ipv4Addr as Encode>::encode_to(ip, output),
             IpAddr::V6(ip) => <Ipv6Addr as Encode>::encode_to(ip, output),
         }
     }
+
+    fn encoded_len(&self) -> usize {
+        match self {
+            IpAddr::V4(ip) => <Ipv4Addr as Encode>::encoded_len(ip),
+            IpAddr::V6(ip) => <Ipv6Addr as Encode>::encoded_len(ip),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -76,7 +83,7 @@ mod tests {
         ];
 
         for (test_name, err, bytes) in test_data {
-            assert_eq!(err, decode::<IpAddr>(&bytes).unwrap_err(), "{test_name}");
+            assert_eq!(err, decode::<IpAddr>(bytes).unwrap_err(), "{test_name}");
         }
     }
 }
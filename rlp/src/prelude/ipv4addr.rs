@@ -6,7 +6,7 @@
 
 use std::net::Ipv4Addr;
 
-use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType};
+use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType, Output};
 
 impl Decode<'_> for Ipv4Addr {
     const TYPE: ItemType = ItemType::SingleValue;
@@ -21,9 +21,13 @@ impl Decode<'_> for Ipv4Addr {
 }
 
 impl Encode for Ipv4Addr {
-    fn encode_to(&self, output: &mut Vec<u8>) {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
         ItemPayloadSlice(&self.octets()).encode_as_single_value(output);
     }
+
+    fn encoded_len(&self) -> usize {
+        ItemPayloadSlice(&self.octets()).encoded_len_as_single_value()
+    }
 }
 
 #[cfg(test)]
@@ -70,7 +74,7 @@ mod tests {
         ];
 
         for (test_name, err, bytes) in test_data {
-            assert_eq!(err, decode::<Ipv4Addr>(&bytes).unwrap_err(), "{test_name}");
+            assert_eq!(err, decode::<Ipv4Addr>(bytes).unwrap_err(), "{test_name}");
         }
     }
 }
@@ -6,14 +6,33 @@
 
 //! Implements RLP serialization for common types.
 
+mod array;
+#[cfg(feature = "num-bigint")]
+mod biguint;
+mod bool;
+mod btree_map;
+mod btree_set;
 mod byte_array;
 mod byte_slice;
 mod byte_vec;
+#[cfg(feature = "bytes")]
+mod bytes;
 mod cow_byte_array;
 mod cow_bytes;
+#[cfg(feature = "std")]
 mod ipaddr;
+#[cfg(feature = "std")]
 mod ipv4addr;
+#[cfg(feature = "std")]
 mod ipv6addr;
+mod option;
+#[cfg(feature = "primitive-types")]
+mod primitive_types;
+#[cfg(feature = "ruint")]
+mod ruint;
+mod string;
+mod string_slice;
+mod tuple;
 mod u256;
 mod u8;
 mod uint;
@@ -0,0 +1,157 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements RLP for `Option<T>`.
+//!
+//! `None` is encoded as the empty item of the type of `T`, i.e. the empty
+//! string (`0x80`) or the empty list (`0xc0`), and `Some(value)` as `value`.
+//! When decoding, an empty payload is decoded as `None`.
+//!
+//! The convention is meant for single value types, e.g. the recipient
+//! address of a contract creation transaction. Note that `Some` of a value
+//! encoded as an empty item, e.g. `Some(0)` or `Some(vec![])`, is
+//! indistinguishable from `None`, and is decoded as `None`. To omit absent
+//! fields at the end of a list, use the derive attribute `#[rlp(trailing)]`
+//! instead.
+
+use crate::{
+    decode_payload, encode_header, Decode, Encode, Error, ItemDataSlice, ItemPayloadSlice,
+    ItemType, Output,
+};
+
+impl<'a, T> Decode<'a> for Option<T>
+where
+    T: Decode<'a>,
+{
+    const TYPE: ItemType = T::TYPE;
+
+    const ACCEPTS_ANY_TYPE: bool = T::ACCEPTS_ANY_TYPE;
+
+    fn decode(payload: ItemPayloadSlice<'a>) -> Result<Self, Error> {
+        if payload.0.is_empty() {
+            return Ok(None);
+        }
+        T::decode(payload).map(Some)
+    }
+
+    fn decode_item(
+        item_type: ItemType,
+        item_data: ItemDataSlice<'a>,
+        payload: ItemPayloadSlice<'a>,
+    ) -> Result<Self, Error> {
+        if payload.0.is_empty() {
+            if T::ACCEPTS_ANY_TYPE {
+                return Ok(None);
+            }
+            return decode_payload(item_type, payload);
+        }
+        T::decode_item(item_type, item_data, payload).map(Some)
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+        match self {
+            Some(value) => value.encode_to(output),
+            None => encode_header(T::encoded_type(), 0, output),
+        }
+    }
+
+    fn encoded_len(&self) -> usize {
+        match self {
+            Some(value) => value.encoded_len(),
+            // The header of the empty item.
+            None => 1,
+        }
+    }
+
+    fn encoded_type() -> ItemType {
+        T::encoded_type()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use crate::{decode, encode, encoded_len, Error, U8};
+
+    #[test]
+    fn test_option() {
+        let test_data = [
+            (Some(65536_u32), &hex!("83010000") as &[u8]),
+            (Some(1), &hex!("01")),
+            (None, &hex!("80")),
+        ];
+
+        for (value, encoded) in test_data {
+            let output = encode(&value);
+            assert_eq!(output, encoded);
+
+            assert_eq!(decode::<Option<u32>>(&output).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_option_of_bytes() {
+        let test_data = [
+            (Some(vec![1, 2]), &hex!("820102") as &[u8]),
+            (None, &hex!("80")),
+        ];
+
+        for (value, encoded) in test_data {
+            let output = encode(&value);
+            assert_eq!(output, encoded);
+            assert_eq!(encoded_len(&value), output.len());
+
+            assert_eq!(decode::<Option<Vec<u8>>>(&output).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_option_of_list() {
+        let test_data = [
+            (Some(vec![U8(1), U8(2)]), &hex!("c20102") as &[u8]),
+            (None, &hex!("c0")),
+        ];
+
+        for (value, encoded) in test_data {
+            let output = encode(&value);
+            assert_eq!(output, encoded);
+            assert_eq!(encoded_len(&value), output.len());
+
+            assert_eq!(decode::<Option<Vec<U8>>>(&output).unwrap(), value);
+        }
+
+        // The empty string isn't a list.
+        assert_eq!(
+            decode::<Option<Vec<U8>>>(&hex!("80")).unwrap_err(),
+            Error::ItemTypeDoesNotMatch
+        );
+    }
+
+    #[test]
+    fn test_some_of_empty_value_is_decoded_as_none() {
+        let output = encode(&Some(0_u32));
+        assert_eq!(output, hex!("80"));
+        assert_eq!(decode::<Option<u32>>(&output).unwrap(), None);
+
+        let output = encode(&Some(Vec::<U8>::new()));
+        assert_eq!(output, hex!("c0"));
+        assert_eq!(decode::<Option<Vec<U8>>>(&output).unwrap(), None);
+    }
+
+    #[test]
+    fn test_vec_of_option() {
+        let data: Vec<Option<&[u8]>> = vec![Some(&[1, 2, 3]), None];
+        let encoded = hex!("c58301020380");
+
+        let output = encode(&data);
+        assert_eq!(output, encoded);
+
+        assert_eq!(decode::<Vec<Option<&[u8]>>>(&output).unwrap(), data);
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements RLP for `primitive_types::{U256, H160, H256}`.
+//!
+//! `U256` is encoded as an unsigned integer, `H160` and `H256` as byte
+//! strings of their fixed length.
+
+use primitive_types::{H160, H256, U256};
+
+use super::uint::{encode_uint_be_bytes, encoded_uint_len, uint_be_bytes};
+use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType, Output};
+
+impl Decode<'_> for U256 {
+    const TYPE: ItemType = ItemType::SingleValue;
+
+    fn decode(payload: ItemPayloadSlice) -> Result<Self, Error> {
+        uint_be_bytes(payload, 32).map(U256::from_big_endian)
+    }
+}
+
+impl Encode for U256 {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+        let mut bytes = [0; 32];
+        self.to_big_endian(&mut bytes);
+        encode_uint_be_bytes(&bytes, output);
+    }
+
+    fn encoded_len(&self) -> usize {
+        encoded_uint_len(self.bits())
+    }
+}
+
+macro_rules! impl_rlp_for_fixed_hash {
+    ($t:ty) => {
+        impl Decode<'_> for $t {
+            const TYPE: ItemType = ItemType::SingleValue;
+
+            fn decode(payload: ItemPayloadSlice) -> Result<Self, Error> {
+                if payload.0.len() != <$t>::len_bytes() {
+                    return Err(Error::InvalidByteRepresentaion);
+                }
+                Ok(<$t>::from_slice(payload.0))
+            }
+        }
+
+        impl Encode for $t {
+            fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+                ItemPayloadSlice(self.as_bytes()).encode_as_single_value(output);
+            }
+
+            fn encoded_len(&self) -> usize {
+                ItemPayloadSlice(self.as_bytes()).encoded_len_as_single_value()
+            }
+        }
+    };
+}
+
+impl_rlp_for_fixed_hash!(H160);
+impl_rlp_for_fixed_hash!(H256);
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use crate::{decode, encode, encoded_len};
+
+    use super::*;
+
+    #[test]
+    fn test_u256() {
+        let test_data = [
+            (U256::zero(), &hex!("80") as &[u8]),
+            (U256::from(0x7f), &hex!("7f")),
+            (U256::from(0x0400), &hex!("820400")),
+            (
+                U256::MAX,
+                &hex!("a0 ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"),
+            ),
+        ];
+
+        for (n, encoded) in test_data {
+            let output = encode(&n);
+            assert_eq!(output, encoded, "{n}");
+            assert_eq!(encoded_len(&n), output.len(), "{n}");
+            assert_eq!(decode::<U256>(&output).unwrap(), n, "{n}");
+        }
+
+        assert_eq!(
+            decode::<U256>(&hex!("820001")).unwrap_err(),
+            Error::UintDecodingFoundLeftPadding
+        );
+        assert_eq!(
+            decode::<U256>(&hex!(
+                "a1 01 0000000000000000000000000000000000000000000000000000000000000000"
+            ))
+            .unwrap_err(),
+            Error::ItemPayloadByteLengthTooLarge
+        );
+    }
+
+    #[test]
+    fn test_fixed_hash() {
+        let address = H160::from(hex!("00000000000000000000000000000000000000ff"));
+        let output = encode(&address);
+        assert_eq!(output, hex!("94 00000000000000000000000000000000000000ff"));
+        assert_eq!(encoded_len(&address), output.len());
+        assert_eq!(decode::<H160>(&output).unwrap(), address);
+
+        let hash = H256::repeat_byte(0xab);
+        assert_eq!(decode::<H256>(&encode(&hash)).unwrap(), hash);
+
+        assert_eq!(
+            decode::<H160>(&hex!("83 0000ff")).unwrap_err(),
+            Error::InvalidByteRepresentaion
+        );
+    }
+}
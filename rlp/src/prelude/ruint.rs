@@ -0,0 +1,84 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements RLP for `ruint::Uint<BITS, LIMBS>`.
+
+use ruint::Uint;
+
+use super::uint::{encode_uint_be_bytes, encoded_uint_len, uint_be_bytes};
+use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType, Output};
+
+impl<const BITS: usize, const LIMBS: usize> Decode<'_> for Uint<BITS, LIMBS> {
+    const TYPE: ItemType = ItemType::SingleValue;
+
+    fn decode(payload: ItemPayloadSlice) -> Result<Self, Error> {
+        let bytes = uint_be_bytes(payload, Self::BYTES)?;
+        // The most significant byte may still exceed `BITS`, e.g. for
+        // `Uint<4, 1>`.
+        Self::try_from_be_slice(bytes).ok_or(Error::ItemPayloadByteLengthTooLarge)
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> Encode for Uint<BITS, LIMBS> {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+        encode_uint_be_bytes(&self.to_be_bytes_vec(), output);
+    }
+
+    fn encoded_len(&self) -> usize {
+        encoded_uint_len(self.bit_len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+    use ruint::aliases::{U256, U64};
+
+    use crate::{decode, encode, encoded_len};
+
+    use super::*;
+
+    #[test]
+    fn test_uint() {
+        let test_data = [
+            (U256::ZERO, &hex!("80") as &[u8]),
+            (U256::from(0x7f), &hex!("7f")),
+            (U256::from(0x0400), &hex!("820400")),
+            (
+                U256::MAX,
+                &hex!("a0 ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"),
+            ),
+        ];
+
+        for (n, encoded) in test_data {
+            let output = encode(&n);
+            assert_eq!(output, encoded, "{n}");
+            assert_eq!(encoded_len(&n), output.len(), "{n}");
+            assert_eq!(decode::<U256>(&output).unwrap(), n, "{n}");
+        }
+
+        // As `u64`.
+        let n = U64::from(0x0102030405_u64);
+        assert_eq!(encode(&n), encode(&0x0102030405_u64));
+    }
+
+    #[test]
+    fn test_decoding_errors() {
+        assert_eq!(
+            decode::<U256>(&hex!("820001")).unwrap_err(),
+            Error::UintDecodingFoundLeftPadding
+        );
+        assert_eq!(
+            decode::<U64>(&hex!("89 010000000000000000")).unwrap_err(),
+            Error::ItemPayloadByteLengthTooLarge
+        );
+        assert_eq!(
+            decode::<Uint<4, 1>>(&hex!("10")).unwrap_err(),
+            Error::ItemPayloadByteLengthTooLarge
+        );
+        assert_eq!(decode::<Uint<4, 1>>(&hex!("0f")).unwrap(), Uint::from(15));
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements RLP for `String`.
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+
+use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType, Output};
+
+impl<'a> Decode<'a> for String {
+    const TYPE: ItemType = ItemType::SingleValue;
+
+    fn decode(payload: ItemPayloadSlice<'a>) -> Result<Self, Error> {
+        <&str as Decode>::decode(payload).map(|s| s.to_owned())
+    }
+}
+
+impl Encode for String {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+        ItemPayloadSlice(self.as_bytes()).encode_as_single_value(output);
+    }
+
+    fn encoded_len(&self) -> usize {
+        ItemPayloadSlice(self.as_bytes()).encoded_len_as_single_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{decode, encode, Error};
+
+    #[test]
+    fn test_string() {
+        let data = "dog".to_string();
+        // The string "dog" from the spec
+        let encoded = &[0x83, b'd', b'o', b'g'];
+
+        let output = encode(&data);
+        assert_eq!(output, encoded);
+
+        assert_eq!(decode::<String>(encoded).unwrap(), data);
+    }
+
+    #[test]
+    fn test_decoding_invalid_utf8() {
+        let encoded = &[0x82, 0xc3, 0x28];
+
+        assert_eq!(
+            decode::<String>(encoded).unwrap_err(),
+            Error::InvalidUtf8String
+        );
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements RLP for `&str`.
+
+use crate::{Decode, Encode, Error, ItemPayloadSlice, ItemType, Output};
+
+impl<'a> Decode<'a> for &'a str {
+    const TYPE: ItemType = ItemType::SingleValue;
+
+    fn decode(payload: ItemPayloadSlice<'a>) -> Result<Self, Error> {
+        core::str::from_utf8(payload.0).map_err(|_| Error::InvalidUtf8String)
+    }
+}
+
+impl Encode for &str {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+        ItemPayloadSlice(self.as_bytes()).encode_as_single_value(output);
+    }
+
+    fn encoded_len(&self) -> usize {
+        ItemPayloadSlice(self.as_bytes()).encoded_len_as_single_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{decode, encode, Error};
+
+    #[test]
+    fn test_string_slice() {
+        let data = "dog";
+        // The string "dog" from the spec
+        let encoded = &[0x83, b'd', b'o', b'g'];
+
+        let output = encode(&data);
+        assert_eq!(output, encoded);
+
+        assert_eq!(decode::<&str>(&output).unwrap(), data);
+    }
+
+    #[test]
+    fn test_empty_string_slice() {
+        let output = encode(&"");
+        assert_eq!(output, &[0x80]);
+
+        assert_eq!(decode::<&str>(&output).unwrap(), "");
+    }
+
+    #[test]
+    fn test_decoding_invalid_utf8() {
+        let encoded = &[0x82, 0xc3, 0x28];
+
+        assert_eq!(
+            decode::<&str>(encoded).unwrap_err(),
+            Error::InvalidUtf8String
+        );
+    }
+}
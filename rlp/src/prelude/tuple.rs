@@ -0,0 +1,119 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements RLP for tuples of up to 12 elements, encoded as lists.
+
+use crate::{
+    encode_header, encode_to, encoded_header_len, encoded_len, Decode, Encode, Error,
+    ItemPayloadSlice, ItemType, Output,
+};
+
+macro_rules! impl_tuple {
+    ($($name:ident $index:tt),+) => {
+        impl<'a, $($name),+> Decode<'a> for ($($name,)+)
+        where
+            $($name: Decode<'a>,)+
+        {
+            const TYPE: ItemType = ItemType::List;
+
+            fn decode(payload: ItemPayloadSlice<'a>) -> Result<Self, Error> {
+                let mut list_iter = payload.list_iter_unchecked();
+                let value = ($(
+                    list_iter.next_item::<$name>().map_err(|e| e.in_list_item($index))?,
+                )+);
+
+                if list_iter.next().is_some() {
+                    return Err(Error::ListDecodingNumberDoesNotMatch);
+                }
+                Ok(value)
+            }
+        }
+
+        impl<$($name: Encode),+> Encode for ($($name,)+) {
+            fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+                let payload_len = 0 $(+ encoded_len(&self.$index))+;
+                encode_header(ItemType::List, payload_len, output);
+                $(encode_to(&self.$index, output);)+
+            }
+
+            fn encoded_len(&self) -> usize {
+                let payload_len = 0 $(+ encoded_len(&self.$index))+;
+                encoded_header_len(payload_len) + payload_len
+            }
+
+            fn encoded_type() -> ItemType {
+                ItemType::List
+            }
+        }
+    };
+}
+
+impl_tuple!(T0 0);
+impl_tuple!(T0 0, T1 1);
+impl_tuple!(T0 0, T1 1, T2 2);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11);
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use crate::{decode, encode, Error};
+
+    #[test]
+    fn test_tuple() {
+        let data: (u16, &[u8], Vec<u16>) = (1, &[1, 2, 3], vec![1, 2, 3]);
+        let encoded = hex!("c90183010203c3010203");
+
+        let output = encode(&data);
+        assert_eq!(output, encoded);
+
+        assert_eq!(decode::<(u16, &[u8], Vec<u16>)>(&output).unwrap(), data);
+    }
+
+    #[test]
+    fn test_tuple_of_12_elements() {
+        let data = (
+            1_u16, 2_u16, 3_u16, 4_u16, 5_u16, 6_u16, 7_u16, 8_u16, 9_u16, 10_u16, 11_u16, 12_u16,
+        );
+        let encoded = hex!("cc0102030405060708090a0b0c");
+
+        let output = encode(&data);
+        assert_eq!(output, encoded);
+
+        assert_eq!(
+            decode::<(u16, u16, u16, u16, u16, u16, u16, u16, u16, u16, u16, u16)>(&output)
+                .unwrap(),
+            data
+        );
+    }
+
+    #[test]
+    fn test_decoding_tuple_errors() {
+        let test_data = [
+            // an extra item
+            (
+                &hex!("c3010203") as &[u8],
+                Error::ListDecodingNumberDoesNotMatch,
+            ),
+            // the last item is missing
+            (&hex!("c101"), Error::ListDecodingIterationEnded),
+            // not a list
+            (&hex!("820102"), Error::ItemTypeDoesNotMatch),
+        ];
+
+        for (encoded, err) in test_data {
+            assert_eq!(decode::<(u16, u16)>(encoded).unwrap_err(), err);
+        }
+    }
+}
@@ -4,7 +4,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use std::mem::size_of;
+use core::mem::size_of;
 
 use ethnum::U256;
 
@@ -15,7 +15,7 @@ impl_encode_for_uint!(U256);
 
 #[inline]
 fn new_u256_from_be_bytes_with_left_padding(bytes: &[u8]) -> U256 {
-    assert!(!bytes.is_empty() && bytes.len() <= std::mem::size_of::<U256>());
+    assert!(!bytes.is_empty() && bytes.len() <= size_of::<U256>());
 
     let mut n_bytes = [0; size_of::<U256>()];
     n_bytes[(size_of::<U256>() - bytes.len())..].copy_from_slice(bytes);
@@ -26,7 +26,7 @@ fn new_u256_from_be_bytes_with_left_padding(bytes: &[u8]) -> U256 {
 mod tests {
     use hex_literal::hex;
 
-    use crate::{decode, encode};
+    use crate::{decode, encode, encoded_len};
 
     use super::*;
 
@@ -38,6 +38,7 @@ mod tests {
         );
 
         let output = encode(&n);
+        assert_eq!(encoded_len(&n), output.len());
         assert_eq!(decode::<U256>(&output).unwrap(), n);
     }
 
@@ -46,6 +47,7 @@ mod tests {
         let output = encode(&U256::ZERO);
         // eth_rlp.py: `encode_uint_0`
         assert_eq!(output, &[0x80]);
+        assert_eq!(encoded_len(&U256::ZERO), 1);
     }
 
     #[test]
@@ -45,6 +45,15 @@ impl U8 {
     pub const fn to_be_bytes(self) -> [u8; 1] {
         self.0.to_be_bytes()
     }
+
+    /// Returns the number of leading zeros in the binary representation of
+    /// this integer.
+    ///
+    /// Implemented to fulfil the macro `impl_encode_for_uint`.
+    #[inline]
+    pub const fn leading_zeros(self) -> u32 {
+        self.0.leading_zeros()
+    }
 }
 
 impl_decode_for_uint!(U8, U8(0), new_u8_newtype_from_be_bytes_with_left_padding);
@@ -58,9 +67,8 @@ pub fn new_u8_newtype_from_be_bytes_with_left_padding(bytes: &[u8]) -> U8 {
 #[cfg(test)]
 mod tests {
     use ::quickcheck_macros::quickcheck;
-    use parity_rlp;
 
-    use crate::{decode, encode};
+    use crate::{decode, encode, encoded_len};
 
     use super::*;
 
@@ -69,7 +77,9 @@ mod tests {
         let parity_rlp_encoded = parity_rlp::encode(&n);
 
         let output = encode(&U8(n));
-        output == parity_rlp_encoded && decode::<U8>(&output).unwrap() == U8(n)
+        output == parity_rlp_encoded
+            && encoded_len(&U8(n)) == output.len()
+            && decode::<U8>(&output).unwrap() == U8(n)
     }
 
     #[test]
@@ -9,27 +9,76 @@
 //! `u8` is excluded, see `U8` for details.
 
 use extensions::{
-    new_u16_from_be_bytes_with_left_padding, new_u32_from_be_bytes_with_left_padding,
-    new_u64_from_be_bytes_with_left_padding,
+    new_u128_from_be_bytes_with_left_padding, new_u16_from_be_bytes_with_left_padding,
+    new_u32_from_be_bytes_with_left_padding, new_u64_from_be_bytes_with_left_padding,
+    strip_left_padding,
 };
 
+use crate::decoding::{is_lenient, reject_non_canonical};
+use crate::{Error, ItemPayloadSlice, Output};
+
+/// Returns the bytes in big endian of the unsigned integer `payload`, empty
+/// for the integer 0.
+///
+/// Returns `Err(Error::ItemPayloadByteLengthTooLarge)` if there are more than
+/// `max_byte_length` bytes, or `Err(Error::UintDecodingFoundLeftPadding)` if
+/// the bytes are left padded with zeroes, unless inside `decode_lenient`
+/// where the left padding is stripped before the length is checked.
+pub(crate) fn uint_be_bytes<'a>(
+    payload: ItemPayloadSlice<'a>,
+    max_byte_length: usize,
+) -> Result<&'a [u8], Error> {
+    if payload.0.first() != Some(&0) {
+        if payload.0.len() > max_byte_length {
+            return Err(Error::ItemPayloadByteLengthTooLarge);
+        }
+        return Ok(payload.0);
+    }
+
+    // Left padded, which is only accepted inside `decode_lenient`.
+    if payload.0.len() > max_byte_length && !is_lenient(payload.0) {
+        return Err(Error::ItemPayloadByteLengthTooLarge);
+    }
+    reject_non_canonical(Error::UintDecodingFoundLeftPadding, payload.0, true)?;
+    let bytes = strip_left_padding(payload.0);
+    if bytes.len() > max_byte_length {
+        return Err(Error::ItemPayloadByteLengthTooLarge);
+    }
+    Ok(bytes)
+}
+
+/// Encodes the unsigned integer of the bytes in big endian `bytes`, stripped
+/// of their left padding, and appends the result to `output`.
+pub(crate) fn encode_uint_be_bytes<O: Output + ?Sized>(bytes: &[u8], output: &mut O) {
+    ItemPayloadSlice(strip_left_padding(bytes)).encode_as_single_value(output);
+}
+
+/// Returns the length in bytes of the encoding of an unsigned integer with
+/// `bit_length` significant bits.
+pub(crate) fn encoded_uint_len(bit_length: usize) -> usize {
+    if bit_length <= 7 {
+        // A single byte in the [0x00, 0x7f] range, or `0x80` for the integer
+        // 0.
+        1
+    } else {
+        let payload_len = bit_length.div_ceil(8);
+        crate::encoded_header_len(payload_len) + payload_len
+    }
+}
+
 macro_rules! impl_decode_for_uint {
     ($t:ty, $new_zero: expr, $fn_uint_from_be_bytes: expr) => {
         impl crate::Decode<'_> for $t {
             const TYPE: crate::ItemType = crate::ItemType::SingleValue;
 
             fn decode(payload: crate::ItemPayloadSlice) -> Result<Self, crate::Error> {
-                if payload.0.len() > std::mem::size_of::<$t>() {
-                    return Err(crate::Error::ItemPayloadByteLengthTooLarge);
-                }
-                if payload.0.is_empty() {
+                let bytes =
+                    crate::prelude::uint::uint_be_bytes(payload, core::mem::size_of::<$t>())?;
+                if bytes.is_empty() {
                     return Ok($new_zero);
                 }
-                if *payload.0.first().unwrap() == 0 {
-                    return Err(crate::Error::UintDecodingFoundLeftPadding);
-                }
 
-                Ok($fn_uint_from_be_bytes(payload.0))
+                Ok($fn_uint_from_be_bytes(bytes))
             }
         }
     };
@@ -41,13 +90,19 @@ pub(crate) use impl_decode_for_uint;
 impl_decode_for_uint!(u16, 0, new_u16_from_be_bytes_with_left_padding);
 impl_decode_for_uint!(u32, 0, new_u32_from_be_bytes_with_left_padding);
 impl_decode_for_uint!(u64, 0, new_u64_from_be_bytes_with_left_padding);
+impl_decode_for_uint!(u128, 0, new_u128_from_be_bytes_with_left_padding);
 
 macro_rules! impl_encode_for_uint {
     ($t:ty) => {
         impl crate::Encode for $t {
-            fn encode_to(&self, output: &mut Vec<u8>) {
-                crate::ItemPayloadSlice(extensions::strip_left_padding(&self.to_be_bytes()))
-                    .encode_as_single_value(output);
+            fn encode_to<O: crate::Output + ?Sized>(&self, output: &mut O) {
+                crate::prelude::uint::encode_uint_be_bytes(&self.to_be_bytes(), output);
+            }
+
+            fn encoded_len(&self) -> usize {
+                crate::prelude::uint::encoded_uint_len(
+                    core::mem::size_of::<$t>() * 8 - self.leading_zeros() as usize,
+                )
             }
         }
     };
@@ -59,13 +114,13 @@ pub(crate) use impl_encode_for_uint;
 impl_encode_for_uint!(u16);
 impl_encode_for_uint!(u32);
 impl_encode_for_uint!(u64);
+impl_encode_for_uint!(u128);
 
 #[cfg(test)]
 mod tests {
     use ::quickcheck_macros::quickcheck;
-    use parity_rlp;
 
-    use crate::{decode, encode, Error};
+    use crate::{decode, encode, encoded_len, Error};
 
     macro_rules! impl_test_int {
         ($test_name:ident, $t:ty) => {
@@ -74,7 +129,9 @@ mod tests {
                 let parity_rlp_encoded = parity_rlp::encode(&n);
 
                 let output = encode(&n);
-                output == parity_rlp_encoded && decode::<$t>(&output).unwrap() == n
+                output == parity_rlp_encoded
+                    && encoded_len(&n) == output.len()
+                    && decode::<$t>(&output).unwrap() == n
             }
         };
     }
@@ -83,6 +140,7 @@ mod tests {
     impl_test_int!(test_u16, u16);
     impl_test_int!(test_u32, u32);
     impl_test_int!(test_u64, u64);
+    impl_test_int!(test_u128, u128);
 
     #[test]
     fn test_decoding_left_padded() {
@@ -92,6 +150,12 @@ mod tests {
             decode::<u64>(left_padded_bytes_rlp_encoded).unwrap_err(),
             Error::UintDecodingFoundLeftPadding
         );
+
+        // The byte length is checked first.
+        assert_eq!(
+            decode::<u16>(&[0x83, 0, 1, 0]).unwrap_err(),
+            Error::ItemPayloadByteLengthTooLarge
+        );
     }
 
     #[test]
@@ -4,7 +4,10 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::{decode_payload, Decode, Error, ItemPayloadSlice, ItemType};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{Decode, Error, ItemPayloadSlice, ItemType};
 
 /// Implements RLP decoding for `Vec<T>`.
 impl<'a, T> Decode<'a> for Vec<T>
@@ -14,19 +17,12 @@ where
     const TYPE: ItemType = ItemType::List;
 
     fn decode(payload: ItemPayloadSlice<'a>) -> Result<Self, Error> {
-        let list_iter = payload.list_iter_unchecked();
+        let mut list_iter = payload.list_iter_unchecked();
 
         let mut v = vec![];
-        for result in list_iter {
-            match result {
-                Ok((item_type, item_payload)) => {
-                    let element = decode_payload(item_type, item_payload)?;
-                    v.push(element);
-                }
-                Err(e) => {
-                    return Err(e);
-                }
-            }
+        while !list_iter.remaining_list_payload().0.is_empty() {
+            let element = list_iter.next_item().map_err(|e| e.in_list_item(v.len()))?;
+            v.push(element);
         }
         Ok(v)
     }
@@ -6,29 +6,78 @@
 
 //! Implements RLP encoding for `Vec<T>` and its slice.
 
-use crate::{encode_to, Encode, ItemPayloadSlice};
+use alloc::vec::Vec;
+
+use crate::encoding::encode_reserved_list_header;
+use crate::{encode_header, encode_to, encoded_header_len, encoded_len, Encode, ItemType, Output};
 
 impl<T: Encode> Encode for &[T] {
-    fn encode_to(&self, output: &mut Vec<u8>) {
-        let mut payload = vec![];
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+        // The lengths of the items of lists of lists would be computed again
+        // at every level, so their headers are encoded after their payloads.
+        if T::encoded_type() == ItemType::List {
+            match output.as_mut_vec() {
+                Some(vec) => encode_list_to_vec(self, vec),
+                None => {
+                    let mut vec = Vec::new();
+                    encode_list_to_vec(self, &mut vec);
+                    output.extend_from_slice(&vec);
+                }
+            }
+            return;
+        }
+
+        encode_header(ItemType::List, payload_len(self), output);
         self.iter().for_each(|element| {
-            encode_to(element, &mut payload);
+            encode_to(element, output);
         });
-        ItemPayloadSlice(&payload).encode_as_list(output);
+    }
+
+    fn encoded_len(&self) -> usize {
+        let payload_len = payload_len(self);
+        encoded_header_len(payload_len) + payload_len
+    }
+
+    fn encoded_type() -> ItemType {
+        ItemType::List
     }
 }
 
 impl<T: Encode> Encode for Vec<T> {
-    fn encode_to(&self, output: &mut Vec<u8>) {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
         <&[T] as Encode>::encode_to(&self.as_slice(), output);
     }
+
+    fn encoded_len(&self) -> usize {
+        <&[T] as Encode>::encoded_len(&self.as_slice())
+    }
+
+    fn encoded_type() -> ItemType {
+        ItemType::List
+    }
+}
+
+/// Encodes `elements` as a list and appends the result to `output`, a byte
+/// being reserved for the header until the payload is encoded.
+fn encode_list_to_vec<T: Encode>(elements: &[T], output: &mut Vec<u8>) {
+    let header_offset = output.len();
+    output.push(0);
+    elements.iter().for_each(|element| {
+        encode_to(element, output);
+    });
+    encode_reserved_list_header(output, header_offset);
+}
+
+/// Returns the length in bytes of the list payload of `elements`.
+fn payload_len<T: Encode>(elements: &[T]) -> usize {
+    elements.iter().map(encoded_len).sum()
 }
 
 #[cfg(test)]
 mod tests {
     use hex_literal::hex;
 
-    use crate::encode;
+    use crate::{encode, encode_to_slice, encoded_len};
 
     #[test]
     fn test_encode_slice_of_u16() {
@@ -65,4 +114,35 @@ mod tests {
         // py_playground: `encode_vec_of_bytes_1_2_3`
         assert_eq!(output, hex!("cc830102038301020383010203"));
     }
+
+    #[test]
+    fn test_encoded_len_of_nested_vec() {
+        let data: Vec<Vec<u64>> = vec![(0..30).collect(), vec![], (0..300).collect()];
+        let output = encode(&data);
+
+        assert_eq!(encoded_len(&data), output.len());
+    }
+
+    #[test]
+    fn test_encode_nested_vec() {
+        let data: Vec<Vec<Vec<u64>>> = (0..10)
+            .map(|i| (0..i).map(|j| (0..j * 20).collect()).collect())
+            .collect();
+        let output = encode(&data);
+
+        let mut stream = parity_rlp::RlpStream::new_list(data.len());
+        for lists in &data {
+            stream.begin_list(lists.len());
+            for list in lists {
+                stream.append_list(list);
+            }
+        }
+        assert_eq!(output, stream.out());
+        assert_eq!(encoded_len(&data), output.len());
+
+        // Into an output other than a `Vec<u8>`.
+        let mut buffer = vec![0; output.len()];
+        assert_eq!(encode_to_slice(&data, &mut buffer), Ok(output.len()));
+        assert_eq!(buffer, output);
+    }
 }
@@ -0,0 +1,214 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements `RlpRaw`.
+
+use crate::{Decode, Encode, Error, ItemDataSlice, ItemPayloadSlice, ItemType, Output, RlpView};
+
+/// A RLP item kept as it is, header included.
+///
+/// It decodes to the data of the item without decoding the payload, and
+/// encodes the data verbatim. It is meant for the sub-structures decoded
+/// lazily, or whose exact bytes matter, e.g. signed records.
+///
+/// Any item decodes to a `RlpRaw`, and an empty item of either type to `None`
+/// of `Option<RlpRaw>`. Its `TYPE` is `ItemType::List`, which is only used
+/// when decoding it from its payload alone, e.g. with `decode_payload`,
+/// returning `Err(Error::RawItemWithoutHeader)`, and to encode `None` of
+/// `Option<RlpRaw>`.
+///
+/// # Examples
+///
+/// ```
+/// use rlp::{decode, encode, Decode, Encode, RlpRaw};
+///
+/// #[derive(Encode, Decode, Debug, PartialEq)]
+/// struct Envelope<'a> {
+///     kind: u16,
+///     body: RlpRaw<'a>,
+/// }
+///
+/// // [1, ["cat", 2]]
+/// let data = [0xc7, 0x01, 0xc5, 0x83, b'c', b'a', b't', 0x02];
+/// let envelope: Envelope = decode(&data).unwrap();
+///
+/// assert_eq!(envelope.body.data().0, &data[2..]);
+/// assert_eq!(envelope.body.decode::<(String, u64)>().unwrap(), ("cat".to_string(), 2));
+/// assert_eq!(encode(&envelope), data);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RlpRaw<'a> {
+    data: &'a [u8],
+    // The header decoded on creation, kept as it may have been accepted by
+    // lenient decoding only.
+    item_type: ItemType,
+    header_len: usize,
+}
+
+impl<'a> RlpRaw<'a> {
+    /// Creates a `RlpRaw` of the item `data`.
+    ///
+    /// Only the header of the item is decoded, and checked to match the
+    /// length of `data`.
+    pub fn new(data: ItemDataSlice<'a>) -> Result<Self, Error> {
+        let (item_type, payload) = data.as_payload()?;
+        Ok(Self::from_parts(data, item_type, payload))
+    }
+
+    fn from_parts(
+        data: ItemDataSlice<'a>,
+        item_type: ItemType,
+        payload: ItemPayloadSlice<'a>,
+    ) -> Self {
+        RlpRaw {
+            data: data.0,
+            item_type,
+            header_len: data.0.len() - payload.0.len(),
+        }
+    }
+
+    /// Returns the data of the item, header included.
+    pub fn data(&self) -> ItemDataSlice<'a> {
+        ItemDataSlice(self.data)
+    }
+
+    /// Returns the type of the item.
+    pub fn item_type(&self) -> ItemType {
+        self.item_type
+    }
+
+    /// Returns the payload of the item.
+    pub fn payload(&self) -> ItemPayloadSlice<'a> {
+        ItemPayloadSlice(&self.data[self.header_len..])
+    }
+
+    /// Decodes the item to a `T`.
+    pub fn decode<T: Decode<'a>>(&self) -> Result<T, Error> {
+        T::decode_item(self.item_type, self.data(), self.payload())
+    }
+
+    /// Returns a `RlpView` of the item.
+    pub fn view(&self) -> RlpView<'a> {
+        RlpView::from_parts(self.data(), self.item_type, self.payload())
+    }
+}
+
+impl<'a> Decode<'a> for RlpRaw<'a> {
+    const TYPE: ItemType = ItemType::List;
+
+    const ACCEPTS_ANY_TYPE: bool = true;
+
+    fn decode(_payload: ItemPayloadSlice<'a>) -> Result<Self, Error> {
+        Err(Error::RawItemWithoutHeader)
+    }
+
+    fn decode_item(
+        item_type: ItemType,
+        item_data: ItemDataSlice<'a>,
+        payload: ItemPayloadSlice<'a>,
+    ) -> Result<Self, Error> {
+        Ok(Self::from_parts(item_data, item_type, payload))
+    }
+}
+
+impl Encode for RlpRaw<'_> {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+        output.extend_from_slice(self.data);
+    }
+
+    fn encoded_len(&self) -> usize {
+        self.data.len()
+    }
+
+    // The same as `Decode::TYPE`.
+    fn encoded_type() -> ItemType {
+        ItemType::List
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use crate::{decode, decode_payload, encode, ListIter, RlpStream};
+
+    use super::*;
+
+    #[test]
+    fn test_raw_items() {
+        // ["cat", [], 1, [2, [3]]]
+        let data = hex!("ca 83636174 c0 01 c302c103");
+
+        let items: Vec<RlpRaw> = decode(&data).unwrap();
+        let bytes: Vec<&[u8]> = items.iter().map(|item| item.data().0).collect();
+        assert_eq!(
+            bytes,
+            [
+                &hex!("83636174") as &[u8],
+                &hex!("c0"),
+                &hex!("01"),
+                &hex!("c302c103")
+            ]
+        );
+        assert_eq!(items[0].item_type(), ItemType::SingleValue);
+        assert_eq!(items[3].item_type(), ItemType::List);
+        assert_eq!(items[3].payload().0, hex!("02c103"));
+        assert_eq!(items[3].decode::<(u64, Vec<u64>)>().unwrap(), (2, vec![3]));
+        assert_eq!(items[3].view().decode_at::<Vec<u64>>(1).unwrap(), [3]);
+
+        assert_eq!(encode(&items), data);
+        assert_eq!(decode::<RlpRaw>(&data).unwrap().data().0, data);
+    }
+
+    #[test]
+    fn test_raw_item_in_stream() {
+        let raw = RlpRaw::new(ItemDataSlice(&hex!("c20102"))).unwrap();
+        let mut stream = RlpStream::new();
+        stream.begin_list().append(&raw).append(&3_u64).end_list();
+        assert_eq!(stream.out(), hex!("c4c2010203"));
+
+        let mut list_iter = ListIter::from_item_data(ItemDataSlice(&hex!("c4c2010203"))).unwrap();
+        assert_eq!(list_iter.next_item::<RlpRaw>().unwrap(), raw);
+        assert_eq!(
+            list_iter
+                .next_item::<RlpRaw>()
+                .unwrap()
+                .decode::<u64>()
+                .unwrap(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_option_of_raw_item() {
+        // An empty item of either type is `None`.
+        assert_eq!(decode::<Option<RlpRaw>>(&hex!("80")).unwrap(), None);
+        assert_eq!(decode::<Option<RlpRaw>>(&hex!("c0")).unwrap(), None);
+        assert_eq!(encode(&None::<RlpRaw>), hex!("c0"));
+
+        for data in [&hex!("83636174") as &[u8], &hex!("c20102"), &hex!("01")] {
+            let raw = decode::<Option<RlpRaw>>(data).unwrap().unwrap();
+            assert_eq!(raw.data().0, data);
+            assert_eq!(encode(&Some(raw)), data);
+        }
+    }
+
+    #[test]
+    fn test_raw_item_errors() {
+        assert_eq!(
+            RlpRaw::new(ItemDataSlice(&hex!("c20102ff"))).unwrap_err(),
+            Error::ItemDataWithInvalidByteLength
+        );
+        assert_eq!(
+            decode::<RlpRaw>(&hex!("8100")).unwrap_err(),
+            Error::SingleByteEncodedAsTwo
+        );
+        assert_eq!(
+            decode_payload::<RlpRaw>(ItemType::List, ItemPayloadSlice(&hex!("0102"))).unwrap_err(),
+            Error::RawItemWithoutHeader
+        );
+    }
+}
@@ -0,0 +1,201 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements `RlpStream`.
+
+use alloc::vec::Vec;
+
+use crate::encoding::encode_reserved_list_header;
+use crate::{encode_to, Encode, ItemDataSlice};
+
+/// A builder to encode RLP items incrementally.
+///
+/// Lists are opened with `begin_list` and closed with `end_list`, the items
+/// appended in between are the items of the innermost open list. The header
+/// of a list is encoded when the list is closed, so the number and the
+/// content of its items don't have to be known up front: a byte is reserved
+/// for it, enough for a payload of up to 55 bytes, and a longer header is
+/// spliced in place.
+///
+/// # Examples
+///
+/// ```
+/// use rlp::RlpStream;
+///
+/// // [ [], [[]], [ [], [[]] ] ]
+/// let mut stream = RlpStream::new();
+/// stream.begin_list();
+/// stream.begin_list().end_list();
+/// stream.begin_list().begin_list().end_list().end_list();
+/// stream.begin_list();
+/// stream.begin_list().end_list();
+/// stream.begin_list().begin_list().end_list().end_list();
+/// stream.end_list();
+/// stream.end_list();
+///
+/// assert_eq!(stream.out(), [0xc7, 0xc0, 0xc1, 0xc0, 0xc3, 0xc0, 0xc1, 0xc0]);
+/// ```
+#[derive(Debug, Default)]
+pub struct RlpStream {
+    output: Vec<u8>,
+    // The offsets in `output` of the header bytes reserved for the open
+    // lists, their payloads start right after.
+    unfinished_lists: Vec<usize>,
+}
+
+impl RlpStream {
+    /// Creates an empty `RlpStream`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Opens a new list, which becomes the list the following items are
+    /// appended to.
+    pub fn begin_list(&mut self) -> &mut Self {
+        self.unfinished_lists.push(self.output.len());
+        self.output.push(0);
+        self
+    }
+
+    /// Closes the innermost open list, and encodes its header.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no open list.
+    pub fn end_list(&mut self) -> &mut Self {
+        let header_offset = self.unfinished_lists.pop().expect("no open list to end");
+        encode_reserved_list_header(&mut self.output, header_offset);
+        self
+    }
+
+    /// Encodes `value` and appends the result.
+    pub fn append<T: Encode>(&mut self, value: &T) -> &mut Self {
+        encode_to(value, &mut self.output);
+        self
+    }
+
+    /// Appends `item_data`, the data of RLP items, as it is.
+    ///
+    /// `item_data` is not checked.
+    pub fn append_raw(&mut self, item_data: ItemDataSlice) -> &mut Self {
+        self.output.extend(item_data.0);
+        self
+    }
+
+    /// Returns true if all the open lists are closed.
+    pub fn is_finished(&self) -> bool {
+        self.unfinished_lists.is_empty()
+    }
+
+    /// Returns the encoded data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are open lists.
+    pub fn out(self) -> Vec<u8> {
+        assert!(self.is_finished(), "lists are not closed");
+        self.output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use crate::encode;
+
+    use super::*;
+
+    #[test]
+    fn test_stream() {
+        let mut stream = RlpStream::new();
+        stream.begin_list();
+        stream.append(&"cat").append(&"dog");
+        stream.begin_list().append(&1_u64).end_list();
+        stream.end_list();
+        let output = stream.out();
+
+        let mut parity_stream = parity_rlp::RlpStream::new_list(3);
+        parity_stream.append(&"cat").append(&"dog");
+        parity_stream.begin_list(1).append(&1_u64);
+        assert_eq!(output, parity_stream.out());
+    }
+
+    #[test]
+    fn test_stream_long_list() {
+        let data: Vec<u64> = (0..1000).collect();
+
+        let mut stream = RlpStream::new();
+        stream.begin_list();
+        data.iter().for_each(|n| {
+            stream.append(n);
+        });
+        stream.end_list();
+
+        assert_eq!(stream.out(), encode(&data));
+    }
+
+    #[test]
+    fn test_stream_nested_long_lists() {
+        let data: Vec<Vec<Vec<u64>>> = (0..10)
+            .map(|i| (0..i).map(|j| (0..j * 20).collect()).collect())
+            .collect();
+
+        let mut stream = RlpStream::new();
+        stream.begin_list();
+        for lists in &data {
+            stream.begin_list();
+            for list in lists {
+                stream.begin_list();
+                list.iter().for_each(|n| {
+                    stream.append(n);
+                });
+                stream.end_list();
+            }
+            stream.end_list();
+        }
+        stream.end_list();
+
+        assert_eq!(stream.out(), encode(&data));
+    }
+
+    #[test]
+    fn test_append_raw() {
+        let mut stream = RlpStream::new();
+        stream.begin_list();
+        // eth_rlp.py: `encode_vec_of_uint_1_2_3`
+        stream.append_raw(ItemDataSlice(&hex!("c3010203")));
+        stream.append(&4_u64);
+        stream.end_list();
+
+        assert_eq!(stream.out(), hex!("c5c301020304"));
+    }
+
+    #[test]
+    fn test_items_outside_list() {
+        let mut stream = RlpStream::new();
+        stream.append(&1_u64).append(&2_u64);
+        assert!(stream.is_finished());
+
+        assert_eq!(stream.out(), hex!("0102"));
+    }
+
+    #[test]
+    #[should_panic(expected = "no open list to end")]
+    fn test_end_list_without_begin_list() {
+        RlpStream::new().end_list();
+    }
+
+    #[test]
+    #[should_panic(expected = "lists are not closed")]
+    fn test_out_with_open_list() {
+        let mut stream = RlpStream::new();
+        stream.begin_list();
+        assert!(!stream.is_finished());
+
+        stream.out();
+    }
+}
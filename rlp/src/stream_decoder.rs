@@ -0,0 +1,315 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements `StreamDecoder`.
+
+use std::io;
+
+use crate::types::HeaderByteLength;
+use crate::{
+    decode_header_unchecked, Decode, Error, ItemDataSlice, ItemPayloadSlice, ItemType,
+    DEFAULT_MAX_ITEM_BYTE_LENGTH,
+};
+
+/// A decoder reading the concatenated top-level RLP items of a
+/// `std::io::Read` one at a time.
+///
+/// The header of an item is read first, and the payload is read only if the
+/// whole item doesn't exceed the maximum length, so the data of an item is
+/// never larger than the maximum in memory.
+///
+/// Reads are not buffered, wrap the reader in a `std::io::BufReader` if small
+/// reads are costly, e.g. for a file.
+///
+/// # Examples
+///
+/// ```
+/// use rlp::{ItemType, StreamDecoder};
+///
+/// // The items `1`, `[2, 3]` and `"dog"`
+/// let data: &[u8] = &[0x01, 0xc2, 0x02, 0x03, 0x83, b'd', b'o', b'g'];
+/// let mut decoder = StreamDecoder::new(data);
+///
+/// let item = decoder.next_item().unwrap().unwrap();
+/// assert_eq!(item.offset(), 0);
+/// assert_eq!(item.decode::<u64>().unwrap(), 1);
+///
+/// let item = decoder.next_item().unwrap().unwrap();
+/// assert_eq!(item.offset(), 1);
+/// assert_eq!(item.item_type(), ItemType::List);
+/// assert_eq!(item.decode::<Vec<u64>>().unwrap(), vec![2, 3]);
+///
+/// let item = decoder.next_item().unwrap().unwrap();
+/// assert_eq!(item.decode::<&str>().unwrap(), "dog");
+///
+/// assert!(decoder.next_item().unwrap().is_none());
+/// assert_eq!(decoder.offset(), 8);
+/// ```
+#[derive(Debug)]
+pub struct StreamDecoder<R> {
+    reader: R,
+    max_item_byte_length: usize,
+    // The number of bytes read from `reader`.
+    offset: u64,
+    // The data of the last item read, reused for the next one.
+    item_data: Vec<u8>,
+}
+
+impl<R: io::Read> StreamDecoder<R> {
+    /// Creates a `StreamDecoder` reading from `reader`, with the maximum item
+    /// length `DEFAULT_MAX_ITEM_BYTE_LENGTH`.
+    pub fn new(reader: R) -> Self {
+        StreamDecoder {
+            reader,
+            max_item_byte_length: DEFAULT_MAX_ITEM_BYTE_LENGTH,
+            offset: 0,
+            item_data: vec![],
+        }
+    }
+
+    /// Sets the maximum length in bytes of an item, header included.
+    ///
+    /// Reading a larger item returns
+    /// `Err(Error::MaximumItemByteLengthExceeded)`.
+    pub fn with_max_item_byte_length(mut self, max_item_byte_length: usize) -> Self {
+        self.max_item_byte_length = max_item_byte_length;
+        self
+    }
+
+    /// Returns the number of bytes read, which is the offset of the next item
+    /// if the last read succeeded.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads the next item.
+    ///
+    /// Returns `Ok(None)` if the reader ends right before an item, and
+    /// `Err(Error::ItemDataWithInvalidByteLength)` if it ends in the middle
+    /// of one. The position of the reader is undefined after an error.
+    pub fn next_item(&mut self) -> Result<Option<StreamItem<'_>>, Error> {
+        let mut first = [0];
+        if !self.read_first_byte(&mut first)? {
+            return Ok(None);
+        }
+        self.item_data.clear();
+        self.item_data.push(first[0]);
+
+        let (header_byte_length, payload_byte_length) = match first[0] {
+            // The byte is its own RLP encoding.
+            0x00..=0x7f => (0, 1),
+            0x80..=0xb7 => (1, (first[0] - 0x80) as u64),
+            0xc0..=0xf7 => (1, (first[0] - 0xc0) as u64),
+            // The header contains the length of the payload length.
+            0xb8..=0xbf | 0xf8..=0xff => {
+                let base_value = if first[0] < 0xc0 { 0xb7 } else { 0xf7 };
+                self.item_data
+                    .resize(1 + (first[0] - base_value) as usize, 0);
+                self.read_exact(1)?;
+
+                let (_, header_byte_length, payload_byte_length) =
+                    decode_header_unchecked(&self.item_data)?;
+                (header_byte_length, payload_byte_length)
+            }
+        };
+
+        let item_byte_length = (header_byte_length as u64)
+            .checked_add(payload_byte_length)
+            .ok_or(Error::MaximumItemByteLengthExceeded)?;
+        if item_byte_length > self.max_item_byte_length as u64 {
+            return Err(Error::MaximumItemByteLengthExceeded);
+        }
+        let read_byte_length = self.item_data.len();
+        if item_byte_length as usize > read_byte_length {
+            self.item_data.resize(item_byte_length as usize, 0);
+            self.read_exact(read_byte_length)?;
+        }
+
+        // Checks the item is canonical, e.g. a single byte is not encoded as
+        // two.
+        let (item_type, _) = ItemDataSlice(&self.item_data).as_payload()?;
+
+        let offset = self.offset;
+        self.offset += self.item_data.len() as u64;
+        Ok(Some(StreamItem {
+            offset,
+            item_type,
+            header_byte_length,
+            data: &self.item_data,
+        }))
+    }
+
+    /// Reads the first byte of an item to `buf`, returns false if the reader
+    /// has ended.
+    fn read_first_byte(&mut self, buf: &mut [u8; 1]) -> Result<bool, Error> {
+        loop {
+            match self.reader.read(buf) {
+                Ok(0) => return Ok(false),
+                Ok(_) => return Ok(true),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(Error::Io(e.kind())),
+            }
+        }
+    }
+
+    /// Reads `item_data[start..]` exactly.
+    fn read_exact(&mut self, start: usize) -> Result<(), Error> {
+        self.reader
+            .read_exact(&mut self.item_data[start..])
+            .map_err(|e| match e.kind() {
+                io::ErrorKind::UnexpectedEof => Error::ItemDataWithInvalidByteLength,
+                kind => Error::Io(kind),
+            })
+    }
+}
+
+/// A top-level item read by `StreamDecoder`.
+#[derive(Clone, Copy, Debug)]
+pub struct StreamItem<'a> {
+    offset: u64,
+    item_type: ItemType,
+    header_byte_length: HeaderByteLength,
+    data: &'a [u8],
+}
+
+impl<'a> StreamItem<'a> {
+    /// Returns the offset of the item in the stream.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the type of the item.
+    pub fn item_type(&self) -> ItemType {
+        self.item_type
+    }
+
+    /// Returns the data of the item, header included.
+    pub fn data(&self) -> ItemDataSlice<'a> {
+        ItemDataSlice(self.data)
+    }
+
+    /// Returns the payload of the item.
+    pub fn payload(&self) -> ItemPayloadSlice<'a> {
+        ItemPayloadSlice(&self.data[self.header_byte_length as usize..])
+    }
+
+    /// Decodes the item to a `T`.
+    pub fn decode<T: Decode<'a>>(&self) -> Result<T, Error> {
+        T::decode_item(self.item_type, self.data(), self.payload())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use crate::encode;
+
+    use super::*;
+
+    #[test]
+    fn test_items() {
+        let long_string = vec![0xab_u8; 1024];
+        let long_list: Vec<u64> = (0..100).collect();
+        let mut data = vec![];
+        data.extend(encode(&long_string));
+        data.extend(encode(&long_list));
+        data.extend(hex!("00"));
+        data.extend(hex!("80"));
+        data.extend(hex!("c0"));
+
+        let mut decoder = StreamDecoder::new(data.as_slice());
+
+        let item = decoder.next_item().unwrap().unwrap();
+        assert_eq!(item.offset(), 0);
+        assert_eq!(item.data().0, encode(&long_string));
+        assert_eq!(item.decode::<Vec<u8>>().unwrap(), long_string);
+
+        let item = decoder.next_item().unwrap().unwrap();
+        assert_eq!(item.offset(), 1027);
+        assert_eq!(item.decode::<Vec<u64>>().unwrap(), long_list);
+
+        let item = decoder.next_item().unwrap().unwrap();
+        assert_eq!(item.item_type(), ItemType::SingleValue);
+        assert_eq!(item.payload().0, &[0x00]);
+
+        let item = decoder.next_item().unwrap().unwrap();
+        assert_eq!(item.item_type(), ItemType::SingleValue);
+        assert!(item.payload().0.is_empty());
+
+        let item = decoder.next_item().unwrap().unwrap();
+        assert_eq!(item.item_type(), ItemType::List);
+        assert!(item.payload().0.is_empty());
+
+        assert!(decoder.next_item().unwrap().is_none());
+        assert_eq!(decoder.offset(), data.len() as u64);
+    }
+
+    #[test]
+    fn test_max_item_byte_length() {
+        // eth_rlp.py: `encode_vec_of_uint_1_2_3`
+        let data = hex!("c3010203c3010203");
+
+        let mut decoder = StreamDecoder::new(&data[..]).with_max_item_byte_length(4);
+        assert_eq!(decoder.next_item().unwrap().unwrap().data().0, &data[..4]);
+
+        let mut decoder = StreamDecoder::new(&data[..]).with_max_item_byte_length(3);
+        assert_eq!(
+            decoder.next_item().unwrap_err(),
+            Error::MaximumItemByteLengthExceeded
+        );
+
+        // The payload length is not read when exceeding the maximum.
+        let data = hex!("bfffffffffffffffff");
+        let mut decoder = StreamDecoder::new(&data[..]);
+        assert_eq!(
+            decoder.next_item().unwrap_err(),
+            Error::MaximumItemByteLengthExceeded
+        );
+    }
+
+    #[test]
+    fn test_decoding_errors() {
+        let test_data = [
+            // the payload is truncated
+            (
+                &hex!("c30102") as &[u8],
+                Error::ItemDataWithInvalidByteLength,
+            ),
+            // the length of the payload length is truncated
+            (&hex!("b901"), Error::ItemDataWithInvalidByteLength),
+            (&hex!("b801"), Error::ShortStringEncodedAsLong),
+            (&hex!("8100"), Error::SingleByteEncodedAsTwo),
+        ];
+
+        for (data, err) in test_data {
+            let mut decoder = StreamDecoder::new(data);
+            assert_eq!(decoder.next_item().unwrap_err(), err);
+        }
+    }
+
+    #[test]
+    fn test_io_error() {
+        struct FailingReader;
+
+        impl io::Read for FailingReader {
+            fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::PermissionDenied.into())
+            }
+        }
+
+        let mut decoder = StreamDecoder::new(FailingReader);
+        assert_eq!(
+            decoder.next_item().unwrap_err(),
+            Error::Io(io::ErrorKind::PermissionDenied)
+        );
+    }
+}
@@ -8,12 +8,80 @@
 
 pub use rlp_derive::*;
 
-use crate::{Error, ItemPayloadSlice, ItemType};
+use alloc::vec::Vec;
+
+use crate::encoder::LenOutput;
+use crate::{decode_payload, Error, ItemDataSlice, ItemPayloadSlice, ItemType};
 
 /// Trait for RLP encoding.
 pub trait Encode {
     /// Encodes `&self` and appends the result to `output`.
-    fn encode_to(&self, output: &mut Vec<u8>);
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O);
+
+    /// Returns the length in bytes of the encoding of `&self`.
+    ///
+    /// Lists use it to encode their headers before the items, so the items can
+    /// be encoded right into the output in a single pass. By default `&self`
+    /// is encoded into an `Output` which only counts the bytes, implementations
+    /// should compute the length without encoding `&self` where they can.
+    fn encoded_len(&self) -> usize {
+        let mut output = LenOutput(0);
+        self.encode_to(&mut output);
+        output.0
+    }
+
+    /// Returns the type of the item `Self` is encoded as,
+    /// `ItemType::SingleValue` by default.
+    ///
+    /// It should be `Decode::TYPE` for the types implementing both. It
+    /// selects the encoding of `None` of `Option<Self>`, the empty item of
+    /// the type.
+    #[inline]
+    fn encoded_type() -> ItemType
+    where
+        Self: Sized,
+    {
+        ItemType::SingleValue
+    }
+}
+
+/// Trait for the destination of RLP encoding.
+///
+/// Implemented for `Vec<u8>`. See `encode_to_writer` and `encode_to_slice` to
+/// encode into a `std::io::Write` or a `&mut [u8]`.
+pub trait Output {
+    /// Appends `byte` to `self`.
+    fn push(&mut self, byte: u8);
+
+    /// Appends `bytes` to `self`.
+    fn extend_from_slice(&mut self, bytes: &[u8]);
+
+    /// Returns `self` as a `Vec<u8>` if it is one, `None` by default.
+    ///
+    /// Lists of lists are encoded right into a `Vec<u8>`, their headers
+    /// encoded once their payloads are, so the lengths of their items are not
+    /// computed up front.
+    #[inline]
+    fn as_mut_vec(&mut self) -> Option<&mut Vec<u8>> {
+        None
+    }
+}
+
+impl Output for Vec<u8> {
+    #[inline]
+    fn push(&mut self, byte: u8) {
+        Vec::push(self, byte);
+    }
+
+    #[inline]
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        Vec::extend_from_slice(self, bytes);
+    }
+
+    #[inline]
+    fn as_mut_vec(&mut self) -> Option<&mut Vec<u8>> {
+        Some(self)
+    }
 }
 
 /// Trait for RLP decoding.
@@ -24,8 +92,59 @@ pub trait Decode<'a> {
     /// primitive types, and `ItemType::List` for Vec<T> or custom structs.
     const TYPE: ItemType;
 
+    /// Whether items of either type decode to `Self`, `false` by default.
+    ///
+    /// It is `true` for the types whose `decode_item` doesn't check the item
+    /// type, e.g. `RlpRaw`, so `Option<Self>` decodes an empty item of either
+    /// type as `None`.
+    const ACCEPTS_ANY_TYPE: bool = false;
+
     /// Decodes `payload` to a `Self`.
     fn decode(payload: ItemPayloadSlice<'a>) -> Result<Self, Error>
     where
         Self: Sized;
+
+    /// Decodes the item `item_data`, of `item_type` and with `payload`, to a
+    /// `Self`.
+    ///
+    /// It is called instead of `decode` whenever the whole item is at hand,
+    /// e.g. by `rlp::decode` and `ListIter::next_item`. The default
+    /// implementation checks `item_type` is `Self::TYPE` and decodes
+    /// `payload`. Types keeping the item as it is, e.g. `RlpRaw`, override
+    /// it.
+    #[inline]
+    fn decode_item(
+        item_type: ItemType,
+        item_data: ItemDataSlice<'a>,
+        payload: ItemPayloadSlice<'a>,
+    ) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        let _ = item_data;
+        decode_payload(item_type, payload)
+    }
 }
+
+/// Trait for the types decoding without borrowing the decoding data, e.g.
+/// `Vec<u8>` and `String`, so the decoded values can outlive it.
+///
+/// It is implemented for all the types implementing `Decode<'a>` for any
+/// lifetime `'a`. The types borrowing the data, e.g. `Cow<[u8]>`, can be
+/// decoded and then converted to owned values, e.g. with `Cow::into_owned`.
+///
+/// # Examples
+///
+/// ```
+/// use rlp::{DecodeOwned, Error};
+///
+/// fn decode_from_buffer<T: DecodeOwned>(buffer: Vec<u8>) -> Result<T, Error> {
+///     rlp::decode(&buffer)
+/// }
+///
+/// let value: String = decode_from_buffer(vec![0x83, b'c', b'a', b't']).unwrap();
+/// assert_eq!(value, "cat");
+/// ```
+pub trait DecodeOwned: for<'a> Decode<'a> {}
+
+impl<T> DecodeOwned for T where T: for<'a> Decode<'a> {}
@@ -9,7 +9,7 @@
 use crate::{Error, ListIter};
 
 /// The type of a RLP item.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum ItemType {
     /// A single item -- a string (i.e. byte array).
     SingleValue,
@@ -0,0 +1,205 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements `validate`.
+
+use alloc::vec;
+
+use crate::{Error, ItemDataSlice, ItemPayloadSlice, ItemType, ListIter};
+
+/// Statistics of the structure of RLP data, returned by `validate`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// The maximum nesting depth of lists, the top-level list is at depth 1,
+    /// as for `Limits::max_depth`.
+    pub max_depth: usize,
+    /// The number of items, lists included.
+    pub item_count: usize,
+    /// The maximum length in bytes of the payloads of the single values.
+    pub max_payload_byte_length: usize,
+}
+
+/// Checks `data` is a single canonically encoded item, and returns the
+/// statistics of its structure.
+///
+/// Every nested item is checked, without decoding to any type: the headers
+/// must be the shortest possible ones, and the lengths must be exact at every
+/// nesting level. The lists are walked without recursion, so there is no
+/// limit on the depth. The non-canonical forms are rejected even inside
+/// `decode_lenient`.
+///
+/// Inside `with_error_context`, the error is returned with the offset and
+/// path of the item where it occurred.
+///
+/// # Examples
+///
+/// ```
+/// use rlp::{validate, with_error_context, Error, PathSegment, Stats};
+///
+/// // [ [], [[]], [ [], [[]] ] ]
+/// let stats = validate(&[0xc7, 0xc0, 0xc1, 0xc0, 0xc3, 0xc0, 0xc1, 0xc0]).unwrap();
+/// assert_eq!(stats.max_depth, 4);
+/// assert_eq!(stats.item_count, 8);
+///
+/// // [1, [0x00 encoded as two bytes]]
+/// let data = [0xc4, 0x01, 0xc2, 0x81, 0x00];
+/// let err = with_error_context(&data, || validate(&data)).unwrap_err();
+/// assert_eq!(err.error(), &Error::SingleByteEncodedAsTwo);
+/// assert_eq!(err.offset(), 3);
+/// assert_eq!(err.path(), [PathSegment::Index(1), PathSegment::Index(0)]);
+/// ```
+pub fn validate(data: &[u8]) -> Result<Stats, Error> {
+    let (item_type, payload) = ItemDataSlice(data)
+        .as_payload()
+        .map_err(|e| e.at_item(ItemDataSlice(data)))?;
+    let mut stats = Stats {
+        item_count: 1,
+        ..Stats::default()
+    };
+    if item_type == ItemType::SingleValue {
+        stats.max_payload_byte_length = payload.0.len();
+        return Ok(stats);
+    }
+
+    // The lists being walked, the innermost last, with the numbers of their
+    // items walked so far.
+    let mut lists = vec![(ListIter::from_list_payload_strict(payload), 0)];
+    stats.max_depth = 1;
+    while let Some((list_iter, walked_count)) = lists.last_mut() {
+        let Some(result) = list_iter.next_itemdata() else {
+            lists.pop();
+            continue;
+        };
+        *walked_count += 1;
+        let (item_type, header_byte_length, item_data) =
+            result.map_err(|e| in_list_items(e, &lists))?;
+        stats.item_count += 1;
+
+        let payload = ItemPayloadSlice(&item_data.0[header_byte_length as usize..]);
+        match item_type {
+            ItemType::SingleValue => {
+                stats.max_payload_byte_length = stats.max_payload_byte_length.max(payload.0.len());
+            }
+            ItemType::List => {
+                lists.push((ListIter::from_list_payload_strict(payload), 0));
+                stats.max_depth = stats.max_depth.max(lists.len());
+            }
+        }
+    }
+    Ok(stats)
+}
+
+// Returns the error `e` of the last item walked of the innermost list of
+// `lists`, recording the path to it.
+fn in_list_items(mut e: Error, lists: &[(ListIter, usize)]) -> Error {
+    for (_, walked_count) in lists.iter().rev() {
+        e = e.in_list_item(walked_count - 1);
+    }
+    e
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use crate::{encode_header, with_error_context, PathSegment};
+
+    use super::*;
+
+    #[test]
+    fn test_valid_data() {
+        let test_data = [
+            // eth_rlp.py: `encode_uint_65536`
+            (
+                &hex!("83010000") as &[u8],
+                Stats {
+                    max_depth: 0,
+                    item_count: 1,
+                    max_payload_byte_length: 3,
+                },
+            ),
+            (
+                &hex!("c0"),
+                Stats {
+                    max_depth: 1,
+                    item_count: 1,
+                    max_payload_byte_length: 0,
+                },
+            ),
+            // ["cat", [1, [0x0400]], []]
+            (
+                &hex!("cb 83636174 c5 01 c3820400 c0"),
+                Stats {
+                    max_depth: 3,
+                    item_count: 7,
+                    max_payload_byte_length: 3,
+                },
+            ),
+        ];
+
+        for (data, stats) in test_data {
+            assert_eq!(validate(data), Ok(stats), "{data:x?}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_data() {
+        let test_data = [
+            (&hex!("") as &[u8], Error::EmptyData, 0, vec![]),
+            (&hex!("8100"), Error::SingleByteEncodedAsTwo, 0, vec![]),
+            // a trailing byte
+            (
+                &hex!("c000"),
+                Error::ItemDataWithInvalidByteLength,
+                0,
+                vec![],
+            ),
+            // [1, <truncated>]
+            (
+                &hex!("c20183"),
+                Error::ItemDataWithInvalidByteLength,
+                2,
+                vec![PathSegment::Index(1)],
+            ),
+            // [[], [1, "a" encoded as long]]
+            (
+                &hex!("c6 c0 c4 01 b80161"),
+                Error::ShortStringEncodedAsLong,
+                4,
+                vec![PathSegment::Index(1), PathSegment::Index(1)],
+            ),
+            // [[<a short list encoded as long>]]
+            (
+                &hex!("c3 c2 f800"),
+                Error::ShortListEncodedAsLong,
+                2,
+                vec![PathSegment::Index(0), PathSegment::Index(0)],
+            ),
+        ];
+
+        for (data, error, offset, path) in test_data {
+            assert_eq!(validate(data).unwrap_err(), error, "{data:x?}");
+
+            let err = with_error_context(data, || validate(data)).unwrap_err();
+            assert_eq!(err.offset(), offset, "{data:x?}");
+            assert_eq!(err.path(), path, "{data:x?}");
+        }
+    }
+
+    #[test]
+    fn test_deeply_nested_lists() {
+        let mut data = vec![0xc0];
+        for _ in 0..10000 {
+            let mut header = vec![];
+            encode_header(ItemType::List, data.len(), &mut header);
+            data = [header, data].concat();
+        }
+
+        let stats = validate(&data).unwrap();
+        assert_eq!(stats.max_depth, 10001);
+        assert_eq!(stats.item_count, 10001);
+    }
+}
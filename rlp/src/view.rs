@@ -0,0 +1,263 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements `RlpView`.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{Decode, Error, ItemDataSlice, ItemPayloadSlice, ItemType, ListIter};
+
+/// A view of a RLP item, to inspect the nested items without decoding the
+/// whole structure.
+///
+/// Only the headers of the items before the accessed one are decoded, the
+/// payloads are neither copied nor decoded until asked for. Accessing the
+/// items of a list by index walks its items from the start every time, unless
+/// the offsets of the items are cached with `with_index_cache`.
+///
+/// # Examples
+///
+/// ```
+/// use rlp::{ItemDataSlice, RlpView};
+///
+/// // ["cat", [1, 2], "dog"]
+/// let data = [0xcb, 0x83, b'c', b'a', b't', 0xc2, 0x01, 0x02, 0x83, b'd', b'o', b'g'];
+/// let view = RlpView::new(ItemDataSlice(&data)).unwrap();
+///
+/// assert!(view.is_list());
+/// assert_eq!(view.item_count().unwrap(), 3);
+/// assert_eq!(view.at(0).unwrap().as_bytes().unwrap(), b"cat");
+/// assert_eq!(view.at(1).unwrap().decode_at::<u64>(1).unwrap(), 2);
+/// assert_eq!(view.decode_at::<&str>(2).unwrap(), "dog");
+/// ```
+#[derive(Clone, Debug)]
+pub struct RlpView<'a> {
+    data: ItemDataSlice<'a>,
+    item_type: ItemType,
+    payload: ItemPayloadSlice<'a>,
+    // The offsets in `payload` where the items of the list start.
+    index: Option<Vec<usize>>,
+}
+
+impl<'a> RlpView<'a> {
+    /// Creates a `RlpView` of the item `data`.
+    ///
+    /// Only the header of the item is decoded, and checked to match the
+    /// length of `data`.
+    pub fn new(data: ItemDataSlice<'a>) -> Result<Self, Error> {
+        let (item_type, payload) = data.as_payload()?;
+        Ok(Self::from_parts(data, item_type, payload))
+    }
+
+    // Creates a `RlpView` of the item `data` with its header already decoded.
+    pub(crate) fn from_parts(
+        data: ItemDataSlice<'a>,
+        item_type: ItemType,
+        payload: ItemPayloadSlice<'a>,
+    ) -> Self {
+        RlpView {
+            data,
+            item_type,
+            payload,
+            index: None,
+        }
+    }
+
+    /// Caches the offsets of the items of the list, so `at` and `item_count`
+    /// don't walk the items any more.
+    ///
+    /// The headers of all the items are decoded. Does nothing for a single
+    /// value.
+    pub fn with_index_cache(mut self) -> Result<Self, Error> {
+        if self.item_type == ItemType::List && self.index.is_none() {
+            let mut list_iter = self.payload.list_iter_unchecked();
+            let mut index = vec![];
+            while !list_iter.remaining_list_payload().0.is_empty() {
+                index.push(self.payload.0.len() - list_iter.remaining_list_payload().0.len());
+                list_iter.next_itemdata().transpose()?;
+            }
+            self.index = Some(index);
+        }
+        Ok(self)
+    }
+
+    /// Returns the data of the item, header included.
+    pub fn data(&self) -> ItemDataSlice<'a> {
+        self.data
+    }
+
+    /// Returns the type of the item.
+    pub fn item_type(&self) -> ItemType {
+        self.item_type
+    }
+
+    /// Returns true if the item is a list.
+    pub fn is_list(&self) -> bool {
+        self.item_type == ItemType::List
+    }
+
+    /// Returns the payload of the item.
+    pub fn payload(&self) -> ItemPayloadSlice<'a> {
+        self.payload
+    }
+
+    /// Returns the bytes of the single value.
+    ///
+    /// Returns `Err(Error::ItemTypeDoesNotMatch)` if the item is a list.
+    pub fn as_bytes(&self) -> Result<&'a [u8], Error> {
+        if self.is_list() {
+            return Err(Error::ItemTypeDoesNotMatch);
+        }
+        Ok(self.payload.0)
+    }
+
+    /// Returns the number of the items of the list.
+    ///
+    /// Returns `Err(Error::ItemTypeDoesNotMatch)` if the item is a single
+    /// value.
+    pub fn item_count(&self) -> Result<usize, Error> {
+        if let Some(ref index) = self.index {
+            return Ok(index.len());
+        }
+
+        let mut list_iter = self.list_iter()?;
+        let mut count = 0;
+        while list_iter.next_itemdata().transpose()?.is_some() {
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Returns a view of the item of the list at `n`.
+    ///
+    /// Returns `Err(Error::ItemTypeDoesNotMatch)` if the item is a single
+    /// value, and `Err(Error::ListItemIndexOutOfRange)` if the list has no
+    /// item at `n`.
+    pub fn at(&self, n: usize) -> Result<RlpView<'a>, Error> {
+        let item_data = match self.index {
+            Some(ref index) => {
+                let start = *index.get(n).ok_or(Error::ListItemIndexOutOfRange)?;
+                let end = index.get(n + 1).copied().unwrap_or(self.payload.0.len());
+                ItemDataSlice(&self.payload.0[start..end])
+            }
+            None => {
+                let mut list_iter = self.list_iter()?;
+                for _ in 0..n {
+                    if list_iter.next_itemdata().transpose()?.is_none() {
+                        return Err(Error::ListItemIndexOutOfRange);
+                    }
+                }
+                let (_, _, item_data) = list_iter
+                    .next_itemdata()
+                    .ok_or(Error::ListItemIndexOutOfRange)??;
+                item_data
+            }
+        };
+        RlpView::new(item_data)
+    }
+
+    /// Decodes the item to a `T`.
+    pub fn decode<T: Decode<'a>>(&self) -> Result<T, Error> {
+        T::decode_item(self.item_type, self.data, self.payload)
+    }
+
+    /// Decodes the item of the list at `n` to a `T`.
+    pub fn decode_at<T: Decode<'a>>(&self, n: usize) -> Result<T, Error> {
+        self.at(n)?.decode().map_err(|e| e.in_list_item(n))
+    }
+
+    /// Returns a `ListIter` over the items of the list.
+    ///
+    /// Returns `Err(Error::ItemTypeDoesNotMatch)` if the item is a single
+    /// value.
+    pub fn list_iter(&self) -> Result<ListIter<'a>, Error> {
+        if !self.is_list() {
+            return Err(Error::ItemTypeDoesNotMatch);
+        }
+        Ok(self.payload.list_iter_unchecked())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use crate::encode;
+
+    use super::*;
+
+    #[test]
+    fn test_view_of_nested_lists() {
+        let value: (u64, Vec<Vec<u64>>, String) = (1, vec![vec![], vec![2, 3]], "dog".to_string());
+        let data = encode(&value);
+
+        for view in [
+            RlpView::new(ItemDataSlice(&data)).unwrap(),
+            RlpView::new(ItemDataSlice(&data))
+                .unwrap()
+                .with_index_cache()
+                .unwrap(),
+        ] {
+            assert_eq!(view.item_count().unwrap(), 3);
+            assert_eq!(view.decode_at::<u64>(0).unwrap(), 1);
+            assert_eq!(view.decode_at::<String>(2).unwrap(), "dog");
+
+            let nested = view.at(1).unwrap();
+            assert!(nested.is_list());
+            assert_eq!(nested.item_count().unwrap(), 2);
+            assert_eq!(nested.at(0).unwrap().item_count().unwrap(), 0);
+            assert_eq!(nested.at(1).unwrap().decode_at::<u64>(1).unwrap(), 3);
+            assert_eq!(nested.at(1).unwrap().data().0, hex!("c20203"));
+
+            assert_eq!(view.at(3).unwrap_err(), Error::ListItemIndexOutOfRange);
+            assert_eq!(
+                view.at(usize::MAX).unwrap_err(),
+                Error::ListItemIndexOutOfRange
+            );
+            assert_eq!(view.as_bytes().unwrap_err(), Error::ItemTypeDoesNotMatch);
+        }
+    }
+
+    #[test]
+    fn test_view_of_single_value() {
+        // eth_rlp.py: `encode_uint_65536`
+        let view = RlpView::new(ItemDataSlice(&hex!("83010000")))
+            .unwrap()
+            .with_index_cache()
+            .unwrap();
+
+        assert!(!view.is_list());
+        assert_eq!(view.as_bytes().unwrap(), hex!("010000"));
+        assert_eq!(view.decode::<u32>().unwrap(), 65536);
+        assert_eq!(view.item_count().unwrap_err(), Error::ItemTypeDoesNotMatch);
+        assert_eq!(view.at(0).unwrap_err(), Error::ItemTypeDoesNotMatch);
+    }
+
+    #[test]
+    fn test_view_errors() {
+        assert_eq!(
+            RlpView::new(ItemDataSlice(&hex!("8100"))).unwrap_err(),
+            Error::SingleByteEncodedAsTwo
+        );
+
+        // [1, <truncated>]
+        let view = RlpView::new(ItemDataSlice(&hex!("c20183"))).unwrap();
+        assert_eq!(view.decode_at::<u64>(0).unwrap(), 1);
+        assert_eq!(
+            view.at(1).unwrap_err(),
+            Error::ItemDataWithInvalidByteLength
+        );
+        assert_eq!(
+            view.item_count().unwrap_err(),
+            Error::ItemDataWithInvalidByteLength
+        );
+        assert_eq!(
+            view.with_index_cache().unwrap_err(),
+            Error::ItemDataWithInvalidByteLength
+        );
+    }
+}
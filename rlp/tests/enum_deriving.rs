@@ -0,0 +1,306 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use hex_literal::hex;
+use rlp::{
+    decode, decode_with_context, encode, encoded_len, Decode, Encode, Error, ItemDataSlice,
+    PathSegment, RlpRaw,
+};
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+enum Message<'a> {
+    Ping(u64),
+    #[rlp(tag = 2)]
+    Pong {
+        request_id: &'a [u8],
+        enr_seq: u64,
+    },
+    #[rlp(tag = 3)]
+    Ack,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+#[rlp(repr = "prefix")]
+enum Envelope<'a> {
+    #[rlp(tag = 1)]
+    Legacy(u64),
+    #[rlp(tag = 2)]
+    Typed(&'a [u8], u64),
+    #[rlp(tag = 3)]
+    Empty,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+enum Opcode {
+    #[rlp(tag = 0)]
+    Stop,
+    #[rlp(tag = 0x80)]
+    Push(u16),
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+#[rlp(repr = "prefix")]
+enum Marker {
+    #[rlp(tag = 0x80)]
+    High,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+#[rlp(repr = "untagged")]
+enum Value {
+    Number(u16),
+    Bytes(Vec<u8>),
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+#[rlp(repr = "untagged")]
+enum Shape {
+    Point(u16, u16),
+    Line { x1: u16, y1: u16, x2: u16, y2: u16 },
+}
+
+#[test]
+fn test_list_repr() {
+    let test_data = [
+        // [tag 0, 7]
+        (Message::Ping(7), &hex!("c28007") as &[u8]),
+        // [tag 2, [b"\x01\x02\x03", 7]]
+        (
+            Message::Pong {
+                request_id: &[1, 2, 3],
+                enr_seq: 7,
+            },
+            &hex!("c702c58301020307"),
+        ),
+        // [tag 3]
+        (Message::Ack, &hex!("c103")),
+    ];
+
+    for (message, encoded) in test_data {
+        let output = encode(&message);
+        assert_eq!(output, encoded);
+        assert_eq!(encoded_len(&message), output.len());
+
+        assert_eq!(decode::<Message>(&output).unwrap(), message);
+    }
+}
+
+#[test]
+fn test_list_repr_decoding_errors() {
+    let test_data = [
+        // unknown tag 9
+        (&hex!("c20907") as &[u8], Error::UnknownEnumVariantTag),
+        // an extra item after the value
+        (&hex!("c3800707"), Error::ListDecodingNumberDoesNotMatch),
+        // the value is missing
+        (&hex!("c102"), Error::ListDecodingIterationEnded),
+        // the value of a struct variant must be a list
+        (&hex!("c50283010203"), Error::ItemTypeDoesNotMatch),
+        // not a list
+        (&hex!("8180"), Error::ItemTypeDoesNotMatch),
+    ];
+
+    for (encoded, err) in test_data {
+        assert_eq!(decode::<Message>(encoded).unwrap_err(), err);
+    }
+}
+
+#[test]
+fn test_vec_of_list_repr() {
+    let v = vec![Message::Ping(7), Message::Ack];
+
+    let encoded = encode(&v);
+    assert_eq!(encoded, hex!("c5c28007c103"));
+
+    assert_eq!(decode::<Vec<Message>>(&encoded).unwrap(), v);
+}
+
+#[test]
+fn test_tags_not_encoded_as_single_byte() {
+    let test_data = [
+        // [0x80 (the integer 0)]
+        (Opcode::Stop, &hex!("c180") as &[u8]),
+        // [0x8180 (the integer 0x80), 7]
+        (Opcode::Push(7), &hex!("c3818007")),
+    ];
+
+    for (opcode, encoded) in test_data {
+        let output = encode(&opcode);
+        assert_eq!(output, encoded);
+        assert_eq!(encoded_len(&opcode), output.len());
+
+        assert_eq!(decode::<Opcode>(&output).unwrap(), opcode);
+    }
+
+    // The tag byte 0x80 is not its own RLP encoding.
+    let output = encode(&Marker::High);
+    assert_eq!(output, hex!("8180"));
+    assert_eq!(encoded_len(&Marker::High), output.len());
+    assert_eq!(decode::<Marker>(&output).unwrap(), Marker::High);
+}
+
+#[test]
+fn test_prefix_repr() {
+    let test_data = [
+        // 0x01 || rlp(7)
+        (Envelope::Legacy(7), &hex!("820107") as &[u8]),
+        // 0x02 || rlp([b"\x01\x02\x03", 7])
+        (Envelope::Typed(&[1, 2, 3], 7), &hex!("8702c58301020307")),
+        // 0x03, a single byte is its own RLP encoding
+        (Envelope::Empty, &hex!("03")),
+    ];
+
+    for (envelope, encoded) in test_data {
+        let output = encode(&envelope);
+        assert_eq!(output, encoded);
+        assert_eq!(encoded_len(&envelope), output.len());
+
+        assert_eq!(decode::<Envelope>(&output).unwrap(), envelope);
+    }
+}
+
+#[test]
+fn test_prefix_repr_decoding_errors() {
+    let test_data = [
+        (&hex!("80") as &[u8], Error::EmptyData),
+        (&hex!("820907"), Error::UnknownEnumVariantTag),
+        // data after the tag of a unit variant
+        (&hex!("820307"), Error::ItemDataWithInvalidByteLength),
+        (&hex!("8402820102"), Error::ItemTypeDoesNotMatch),
+        (&hex!("c0"), Error::ItemTypeDoesNotMatch),
+    ];
+
+    for (encoded, err) in test_data {
+        assert_eq!(decode::<Envelope>(encoded).unwrap_err(), err);
+    }
+}
+
+#[test]
+fn test_untagged_repr() {
+    let test_data = [
+        (Value::Number(1), &hex!("01") as &[u8]),
+        (Value::Bytes(vec![1, 2, 3]), &hex!("83010203")),
+    ];
+
+    for (value, encoded) in test_data {
+        let output = encode(&value);
+        assert_eq!(output, encoded);
+        assert_eq!(encoded_len(&value), output.len());
+
+        assert_eq!(decode::<Value>(&output).unwrap(), value);
+    }
+
+    let test_data = [
+        (Shape::Point(1, 2), &hex!("c20102") as &[u8]),
+        (
+            Shape::Line {
+                x1: 1,
+                y1: 2,
+                x2: 3,
+                y2: 4,
+            },
+            &hex!("c401020304"),
+        ),
+    ];
+
+    for (shape, encoded) in test_data {
+        let output = encode(&shape);
+        assert_eq!(output, encoded);
+        assert_eq!(encoded_len(&shape), output.len());
+
+        assert_eq!(decode::<Shape>(&output).unwrap(), shape);
+    }
+}
+
+#[test]
+fn test_untagged_repr_decoding_errors() {
+    assert_eq!(
+        decode::<Shape>(&hex!("c3010203")).unwrap_err(),
+        Error::NoEnumVariantMatched
+    );
+    assert_eq!(
+        decode::<Value>(&hex!("c0")).unwrap_err(),
+        Error::ItemTypeDoesNotMatch
+    );
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+enum Either<'l, 'r, L, R> {
+    Left(L, &'l str),
+    Right(R, &'r [u8]),
+}
+
+#[test]
+fn test_generic_enum() {
+    let test_data = [
+        // [0, [1, "a"]]
+        (Either::Left(1_u64, "a"), &hex!("c480c20161") as &[u8]),
+        // [1, ["cat", 0x02]]
+        (
+            Either::Right("cat".to_string(), &[2]),
+            &hex!("c701c58363617402"),
+        ),
+    ];
+
+    for (either, encoded) in test_data {
+        assert_eq!(encode(&either), encoded);
+        assert_eq!(decode::<Either<u64, String>>(encoded).unwrap(), either);
+    }
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Drawing {
+    shapes: Vec<Shape>,
+    scale: u16,
+}
+
+#[test]
+fn test_untagged_repr_decoding_error_context() {
+    // [[[1, 2, 3, 65536]], 1024]: the failed attempts to decode the shape
+    // record no context of their own.
+    let encoded = hex!("ccc8c7010203830100008204 00");
+    let err = decode_with_context::<Drawing>(&encoded).unwrap_err();
+    assert_eq!(err.error(), &Error::NoEnumVariantMatched);
+    assert_eq!(
+        err.path(),
+        [
+            PathSegment::Field {
+                type_name: "Drawing",
+                name: "shapes"
+            },
+            PathSegment::Index(0),
+        ]
+    );
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+#[rlp(repr = "untagged")]
+enum Item<'a> {
+    Number(u64),
+    Raw(RlpRaw<'a>),
+}
+
+#[test]
+fn test_untagged_repr_of_raw_item() {
+    let raw = |data| RlpRaw::new(ItemDataSlice(data)).unwrap();
+    let test_data = [
+        (Item::Number(1), &hex!("01") as &[u8]),
+        (Item::Raw(raw(&hex!("c20102"))), &hex!("c20102")),
+    ];
+
+    for (item, encoded) in test_data {
+        let output = encode(&item);
+        assert_eq!(output, encoded);
+        assert_eq!(encoded_len(&item), output.len());
+
+        assert_eq!(decode::<Item>(&output).unwrap(), item);
+    }
+
+    // [1, [1, 2]]
+    let encoded = hex!("c4 01 c20102");
+    let items: Vec<Item> = decode(&encoded).unwrap();
+    assert_eq!(items, [Item::Number(1), Item::Raw(raw(&hex!("c20102")))]);
+}
@@ -0,0 +1,251 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use hex_literal::hex;
+use rlp::{decode, encode, encoded_len, Decode, Encode, Error, ItemDataSlice, RlpRaw};
+
+/// Encodes `u64` as 8 bytes with the left padding.
+mod u64_fixed_bytes {
+    use rlp::{decode_payload, Error, ItemDataSlice, ItemPayloadSlice, ItemType};
+
+    pub fn encode_to<O: rlp::Output + ?Sized>(value: &u64, output: &mut O) {
+        rlp::encode_to(&value.to_be_bytes(), output);
+    }
+
+    pub fn encoded_len(value: &u64) -> usize {
+        rlp::encoded_len(&value.to_be_bytes())
+    }
+
+    pub fn decode(
+        item_type: ItemType,
+        _item_data: ItemDataSlice,
+        payload: ItemPayloadSlice,
+    ) -> Result<u64, Error> {
+        let bytes: [u8; 8] = decode_payload(item_type, payload)?;
+        Ok(u64::from_be_bytes(bytes))
+    }
+}
+
+/// Keeps a list item as it is.
+mod raw_list {
+    use rlp::{Error, ItemDataSlice, ItemPayloadSlice, ItemType, RlpRaw};
+
+    pub fn encode_to<O: rlp::Output + ?Sized>(value: &RlpRaw, output: &mut O) {
+        rlp::encode_to(value, output);
+    }
+
+    pub fn encoded_len(value: &RlpRaw) -> usize {
+        rlp::encoded_len(value)
+    }
+
+    pub fn decode<'a>(
+        item_type: ItemType,
+        item_data: ItemDataSlice<'a>,
+        payload: ItemPayloadSlice<'a>,
+    ) -> Result<RlpRaw<'a>, Error> {
+        if item_type != ItemType::List {
+            return Err(Error::ItemTypeDoesNotMatch);
+        }
+        rlp::Decode::decode_item(item_type, item_data, payload)
+    }
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Transaction {
+    nonce: u64,
+    #[rlp(with = "u64_fixed_bytes")]
+    gas: u64,
+    #[rlp(skip)]
+    hash: Option<u64>,
+    #[rlp(default)]
+    value: u64,
+    #[rlp(trailing)]
+    chain_id: Option<u64>,
+    #[rlp(trailing)]
+    data: Option<Vec<u8>>,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+enum Message {
+    Record(u16, #[rlp(trailing)] Option<u16>),
+}
+
+#[test]
+fn test_transaction() {
+    let test_data = [
+        (
+            Transaction {
+                nonce: 1,
+                gas: 2,
+                hash: None,
+                value: 3,
+                chain_id: Some(4),
+                data: Some(vec![5, 6]),
+            },
+            &hex!("cf018800000000000000020304820506") as &[u8],
+        ),
+        // the list ends at `chain_id`
+        (
+            Transaction {
+                nonce: 1,
+                gas: 2,
+                hash: None,
+                value: 3,
+                chain_id: None,
+                data: None,
+            },
+            &hex!("cb0188000000000000000203"),
+        ),
+    ];
+
+    for (transaction, encoded) in test_data {
+        let output = encode(&transaction);
+        assert_eq!(output, encoded);
+        assert_eq!(encoded_len(&transaction), output.len());
+
+        assert_eq!(decode::<Transaction>(&output).unwrap(), transaction);
+    }
+}
+
+#[test]
+fn test_skip_is_not_encoded() {
+    let transaction = Transaction {
+        nonce: 1,
+        gas: 2,
+        hash: Some(7),
+        value: 3,
+        chain_id: None,
+        data: None,
+    };
+
+    let encoded = encode(&transaction);
+    assert_eq!(encoded, hex!("cb0188000000000000000203"));
+
+    let decoded: Transaction = decode(&encoded).unwrap();
+    assert_eq!(decoded.hash, None);
+}
+
+#[test]
+fn test_trailing_none_ends_the_list() {
+    let transaction = Transaction {
+        nonce: 1,
+        gas: 2,
+        hash: None,
+        value: 3,
+        chain_id: None,
+        data: Some(vec![5, 6]),
+    };
+
+    // `data` is dropped for `chain_id` is `None`.
+    assert_eq!(encode(&transaction), hex!("cb0188000000000000000203"));
+}
+
+#[test]
+fn test_decoding_absent_default() {
+    let decoded: Transaction = decode(&hex!("ca01880000000000000002")).unwrap();
+    assert_eq!(
+        decoded,
+        Transaction {
+            nonce: 1,
+            gas: 2,
+            hash: None,
+            value: 0,
+            chain_id: None,
+            data: None,
+        }
+    );
+}
+
+#[test]
+fn test_decoding_transaction_errors() {
+    let test_data = [
+        // `gas` is missing
+        (&hex!("c101") as &[u8], Error::ListDecodingIterationEnded),
+        // `gas` must be 8 bytes
+        (&hex!("c20102"), Error::InvalidByteRepresentaion),
+        // an extra item after `data`
+        (
+            &hex!("d001880000000000000002030482050680"),
+            Error::ListDecodingNumberDoesNotMatch,
+        ),
+    ];
+
+    for (encoded, err) in test_data {
+        assert_eq!(decode::<Transaction>(encoded).unwrap_err(), err);
+    }
+}
+
+#[test]
+fn test_enum_variant_trailing() {
+    let test_data = [
+        (Message::Record(1, Some(2)), &hex!("c480c20102") as &[u8]),
+        (Message::Record(1, None), &hex!("c380c101")),
+    ];
+
+    for (message, encoded) in test_data {
+        let output = encode(&message);
+        assert_eq!(output, encoded);
+
+        assert_eq!(decode::<Message>(&output).unwrap(), message);
+    }
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Envelope<'a> {
+    #[rlp(with = "raw_list")]
+    body: RlpRaw<'a>,
+    #[rlp(default)]
+    extra: Option<RlpRaw<'a>>,
+    #[rlp(trailing)]
+    signature: Option<RlpRaw<'a>>,
+}
+
+#[test]
+fn test_raw_fields() {
+    let raw = |data| RlpRaw::new(ItemDataSlice(data)).unwrap();
+    let test_data = [
+        // [[1, 2], "cat", [3]]
+        (
+            Envelope {
+                body: raw(&hex!("c20102")),
+                extra: Some(raw(&hex!("83636174"))),
+                signature: Some(raw(&hex!("c103"))),
+            },
+            &hex!("c9 c20102 83636174 c103") as &[u8],
+        ),
+        // [[], [[]]]
+        (
+            Envelope {
+                body: raw(&hex!("c0")),
+                extra: Some(raw(&hex!("c1c0"))),
+                signature: None,
+            },
+            &hex!("c3 c0 c1c0"),
+        ),
+    ];
+
+    for (envelope, encoded) in test_data {
+        let output = encode(&envelope);
+        assert_eq!(output, encoded);
+        assert_eq!(encoded_len(&envelope), output.len());
+
+        assert_eq!(decode::<Envelope>(&output).unwrap(), envelope);
+    }
+
+    // [[]]
+    assert_eq!(
+        decode::<Envelope>(&hex!("c1c0")).unwrap(),
+        Envelope {
+            body: raw(&hex!("c0")),
+            extra: None,
+            signature: None,
+        }
+    );
+    assert_eq!(
+        decode::<Envelope>(&hex!("c101")).unwrap_err(),
+        Error::ItemTypeDoesNotMatch
+    );
+}
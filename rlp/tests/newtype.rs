@@ -7,15 +7,22 @@
 //! Examples for newtype RLP serialization.
 
 use hex_literal::hex;
-use rlp::{decode, encode, encode_to, Decode, Encode, Error, ItemPayloadSlice, ItemType};
+use rlp::{
+    decode, encode, encode_to, encoded_len, Decode, Encode, Error, ItemPayloadSlice, ItemType,
+    Output,
+};
 
 #[derive(Debug, PartialEq)]
 struct Signature<'a>(&'a [u8]);
 
 impl Encode for Signature<'_> {
-    fn encode_to(&self, output: &mut Vec<u8>) {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
         encode_to(&self.0, output);
     }
+
+    fn encoded_len(&self) -> usize {
+        encoded_len(&self.0)
+    }
 }
 
 impl<'a> Decode<'a> for Signature<'a> {
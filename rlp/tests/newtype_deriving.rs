@@ -5,7 +5,7 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use hex_literal::hex;
-use rlp::{decode, encode, Decode, Encode};
+use rlp::{decode, encode, Decode, Encode, ItemDataSlice, RlpRaw};
 
 #[derive(Encode, Decode, Debug, PartialEq)]
 struct Signature<'a>(&'a [u8]);
@@ -33,3 +33,35 @@ fn test_vec_of_signature() {
     let vec_of_signature_decoded: Vec<Signature> = decode(&encoded).unwrap();
     assert_eq!(vec_of_signature_decoded, v);
 }
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Numbers(Vec<u64>);
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Body<'a>(RlpRaw<'a>);
+
+#[test]
+fn test_newtype_of_list() {
+    let numbers = Numbers(vec![1, 2]);
+    let encoded = encode(&numbers);
+    assert_eq!(&encoded, &hex!("c20102"));
+
+    assert_eq!(decode::<Numbers>(&encoded).unwrap(), numbers);
+}
+
+#[test]
+fn test_newtype_of_raw_item() {
+    for data in [&hex!("c20102") as &[u8], &hex!("83636174"), &hex!("01")] {
+        let body = Body(RlpRaw::new(ItemDataSlice(data)).unwrap());
+        assert_eq!(encode(&body), data);
+
+        assert_eq!(decode::<Body>(data).unwrap(), body);
+    }
+
+    // [[1, 2], "cat"]
+    let encoded = hex!("c7 c20102 83636174");
+    let bodies: Vec<Body> = decode(&encoded).unwrap();
+    assert_eq!(bodies[0].0.decode::<Vec<u64>>().unwrap(), [1, 2]);
+    assert_eq!(bodies[1].0.decode::<&str>().unwrap(), "cat");
+    assert_eq!(encode(&bodies), encoded);
+}
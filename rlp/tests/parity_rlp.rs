@@ -8,7 +8,10 @@
 //! https://github.com/paritytech/parity-common/tree/master/rlp/tests
 
 use hex_literal::hex;
-use rlp::{decode, encode, encode_to, Decode, Encode, Error, ItemPayloadSlice, ItemType};
+use rlp::{
+    decode, encode, encode_to, encoded_header_len, encoded_len, Decode, Encode, Error,
+    ItemPayloadSlice, ItemType, Output,
+};
 
 #[test]
 fn test_rlp_data_length_check() {
@@ -22,9 +25,7 @@ fn test_rlp_data_length_check() {
 #[test]
 fn test_rlp_long_data_length_check() {
     let mut data = hex!("b8ff").to_vec();
-    for _ in 0..253 {
-        data.push(b'c');
-    }
+    data.extend([b'c'; 253]);
 
     assert_eq!(
         decode::<&[u8]>(&data).unwrap_err(),
@@ -35,9 +36,7 @@ fn test_rlp_long_data_length_check() {
 #[test]
 fn test_the_exact_long_string() {
     let mut data = hex!("b8ff").to_vec();
-    for _ in 0..255 {
-        data.push(b'c');
-    }
+    data.extend([b'c'; 255]);
 
     assert!(decode::<&[u8]>(&data).is_ok());
 }
@@ -45,9 +44,7 @@ fn test_the_exact_long_string() {
 #[test]
 fn test_rlp_2bytes_data_length_check() {
     let mut data = hex!("b902ff").to_vec(); // 512+255
-    for _ in 0..700 {
-        data.push(b'c');
-    }
+    data.extend([b'c'; 700]);
 
     assert_eq!(
         decode::<&[u8]>(&data).unwrap_err(),
@@ -120,13 +117,18 @@ fn test_nested_list_roundtrip() {
     struct Inner(u64, u64);
 
     impl Encode for Inner {
-        fn encode_to(&self, output: &mut Vec<u8>) {
+        fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
             let mut payload = vec![];
             encode_to(&self.0, &mut payload);
             encode_to(&self.1, &mut payload);
 
             ItemPayloadSlice(&payload).encode_as_list(output);
         }
+
+        fn encoded_len(&self) -> usize {
+            let payload_len = encoded_len(&self.0) + encoded_len(&self.1);
+            encoded_header_len(payload_len) + payload_len
+        }
     }
 
     impl<'a> Decode<'a> for Inner {
@@ -148,9 +150,13 @@ fn test_nested_list_roundtrip() {
     struct Nest<T>(Vec<T>);
 
     impl<T: Encode> Encode for Nest<T> {
-        fn encode_to(&self, output: &mut Vec<u8>) {
+        fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
             encode_to(&self.0, output);
         }
+
+        fn encoded_len(&self) -> usize {
+            encoded_len(&self.0)
+        }
     }
 
     impl<'a, T: Decode<'a>> Decode<'a> for Nest<T> {
@@ -11,8 +11,9 @@ use std::fs::File;
 use std::path::PathBuf;
 
 use num_bigint::BigUint;
-use rlp_types::json::{decode_rlp_to_json_value, encode_json_value_to_rlp};
-use rlp_types::RlpBigUint;
+use rlp_types::json::{
+    decode_rlp_to_json_value, encode_json_value_to_rlp, try_encode_json_value_to_rlp,
+};
 use serde_json::Value;
 
 use rlp::{decode, encode, Error};
@@ -29,13 +30,16 @@ fn rlptest() {
         // Cases involve big int
         if let Some(in_str) = d["in"].as_str() {
             if let Some(in_decimal) = in_str.strip_prefix("#") {
-                let n = RlpBigUint(BigUint::parse_bytes(in_decimal.as_bytes(), 10).unwrap());
+                let n = BigUint::parse_bytes(in_decimal.as_bytes(), 10).unwrap();
                 let encoded = encode(&n);
                 assert_eq!(&encoded, &rlp_data);
 
-                let decoded: RlpBigUint = decode(&rlp_data).unwrap();
+                let decoded: BigUint = decode(&rlp_data).unwrap();
                 assert_eq!(decoded, n, "name: {name}");
 
+                let encoded = try_encode_json_value_to_rlp(&d["in"]).unwrap();
+                assert_eq!(&encoded, &rlp_data);
+
                 continue;
             }
         };
@@ -60,12 +64,12 @@ fn invalid_rlptest() {
     let cases = root.as_object().unwrap();
     for (name, d) in cases {
         let out = d["out"].as_str().unwrap();
-        let rlp_hex = out.strip_prefix("0x").unwrap_or(&out);
+        let rlp_hex = out.strip_prefix("0x").unwrap_or(out);
         let rlp_data = hex::decode(rlp_hex).unwrap();
 
         if name == "leadingZerosInLongLengthArray1" {
             assert_eq!(
-                decode::<RlpBigUint>(&rlp_data).unwrap_err(),
+                decode::<BigUint>(&rlp_data).unwrap_err(),
                 Error::UintDecodingFoundLeftPadding
             );
             continue;
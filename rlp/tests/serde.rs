@@ -0,0 +1,152 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+#![cfg(feature = "serde")]
+
+use std::collections::BTreeMap;
+
+use hex_literal::hex;
+use rlp::{decode, encode, from_slice, to_vec, Decode, Encode, Error};
+use serde::{Deserialize, Serialize};
+
+#[derive(Encode, Decode, Serialize, Deserialize, Debug, PartialEq)]
+struct Entry<'a> {
+    id: u16,
+    #[serde(with = "serde_bytes")]
+    field1: &'a [u8],
+    name: String,
+    enabled: bool,
+}
+
+#[derive(Encode, Decode, Serialize, Deserialize, Debug, PartialEq)]
+struct Block<'a> {
+    number: u64,
+    #[serde(borrow)]
+    entries: Vec<Entry<'a>>,
+    parent: Option<u32>,
+    tags: (u16, u32),
+}
+
+#[derive(Encode, Decode, Serialize, Deserialize, Debug, PartialEq)]
+enum Message {
+    Ping,
+    Pong(u64),
+    Data(u64, String),
+    Request { id: u32, total: u64 },
+}
+
+#[test]
+fn test_struct_matches_derive() {
+    let block = Block {
+        number: 65536,
+        entries: vec![
+            Entry {
+                id: 1,
+                field1: &[1, 2, 3],
+                name: "cat".to_string(),
+                enabled: true,
+            },
+            Entry {
+                id: 0,
+                field1: &[],
+                name: String::new(),
+                enabled: false,
+            },
+        ],
+        parent: Some(3),
+        tags: (0, 1024),
+    };
+
+    let serialized = to_vec(&block).unwrap();
+    assert_eq!(serialized, encode(&block));
+    assert_eq!(from_slice::<Block>(&serialized).unwrap(), block);
+}
+
+#[test]
+fn test_enum_matches_derive() {
+    let messages = [
+        Message::Ping,
+        Message::Pong(7),
+        Message::Data(1, "dog".to_string()),
+        Message::Request { id: 2, total: 3 },
+    ];
+
+    for message in messages {
+        let serialized = to_vec(&message).unwrap();
+        assert_eq!(serialized, encode(&message), "{message:?}");
+        assert_eq!(decode::<Message>(&serialized).unwrap(), message);
+        assert_eq!(from_slice::<Message>(&serialized).unwrap(), message);
+    }
+}
+
+#[test]
+fn test_newtype_and_unit() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Id(u32);
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Marker;
+
+    // eth_rlp.py: `encode_uint_65536`
+    assert_eq!(to_vec(&Id(65536)).unwrap(), hex!("83010000"));
+    assert_eq!(from_slice::<Id>(&hex!("83010000")).unwrap(), Id(65536));
+
+    assert_eq!(to_vec(&Marker).unwrap(), hex!("c0"));
+    assert_eq!(from_slice::<Marker>(&hex!("c0")).unwrap(), Marker);
+    assert_eq!(to_vec(&()).unwrap(), hex!("c0"));
+}
+
+#[test]
+fn test_map() {
+    let map = BTreeMap::from([(1_u64, "cat".to_string()), (2, "dog".to_string())]);
+
+    let serialized = to_vec(&map).unwrap();
+    // [[1, "cat"], [2, "dog"]]
+    assert_eq!(serialized, hex!("ccc50183636174c50283646f67"));
+    assert_eq!(
+        from_slice::<BTreeMap<u64, String>>(&serialized).unwrap(),
+        map
+    );
+}
+
+#[test]
+fn test_unsupported_types() {
+    assert_eq!(
+        to_vec(&-1_i32).unwrap_err(),
+        Error::Serde("i32 is not supported by RLP".to_string())
+    );
+    assert_eq!(
+        to_vec(&1.5_f64).unwrap_err(),
+        Error::Serde("f64 is not supported by RLP".to_string())
+    );
+    assert!(from_slice::<i64>(&hex!("01")).is_err());
+}
+
+#[test]
+fn test_decoding_errors() {
+    let test_data = [
+        // a single byte encoded as two
+        (&hex!("c3810001") as &[u8], Error::SingleByteEncodedAsTwo),
+        // a uint with left padding
+        (&hex!("c482000103"), Error::UintDecodingFoundLeftPadding),
+        // an extra item
+        (&hex!("c3010203"), Error::ListDecodingNumberDoesNotMatch),
+        // a missing item
+        (
+            &hex!("c101"),
+            Error::Serde("invalid length 1, expected a tuple of size 2".to_string()),
+        ),
+        (&hex!("01"), Error::ItemTypeDoesNotMatch),
+    ];
+
+    for (data, err) in test_data {
+        assert_eq!(
+            from_slice::<(u16, u64)>(data).unwrap_err(),
+            err,
+            "{data:x?}"
+        );
+    }
+}
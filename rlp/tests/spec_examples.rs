@@ -5,12 +5,13 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 /// Tests against the examples from the spec.
-use rlp::{decode, encode, Decode, Encode, Error, ItemPayloadSlice, ItemType, U8};
+use rlp::{decode, encode, Decode, Encode, Error, ItemPayloadSlice, ItemType, Output, U8};
+use rlp_types::bracket;
 
 #[test]
 fn the_string_dog() {
     let data = b"dog";
-    let encoded = &[0x83, b'd', b'o', b'g'];
+    let encoded = bracket!(r#""dog""#);
 
     let output = encode(data);
     assert_eq!(output, encoded);
@@ -22,7 +23,7 @@ fn the_string_dog() {
 #[test]
 fn the_list_cat_dog() {
     let data: &[&[u8]] = &[b"cat", b"dog"];
-    let encoded = &[0xc8, 0x83, b'c', b'a', b't', 0x83, b'd', b'o', b'g'];
+    let encoded = bracket!(r#"["cat", "dog"]"#);
 
     let output = encode(&data);
     assert_eq!(output, encoded);
@@ -34,7 +35,7 @@ fn the_list_cat_dog() {
 #[test]
 fn the_empty_string() {
     let data = b"";
-    let encoded = &[0x80];
+    let encoded = bracket!(r#""""#);
 
     let output = encode(data);
     assert_eq!(output, encoded);
@@ -46,7 +47,7 @@ fn the_empty_string() {
 #[test]
 fn the_empty_list() {
     let data: &[&[u8]] = &[];
-    let encoded = &[0xc0];
+    let encoded = bracket!("[]");
 
     let output = encode(&data);
     assert_eq!(output, encoded);
@@ -58,7 +59,7 @@ fn the_empty_list() {
 #[test]
 fn the_integer_0() {
     let data = U8(0);
-    let encoded = &[0x80];
+    let encoded = bracket!("0");
 
     let output = encode(&data);
     assert_eq!(output, encoded);
@@ -70,7 +71,7 @@ fn the_integer_0() {
 #[test]
 fn the_encoded_integer_0() {
     let data: &[u8] = &[0x00];
-    let encoded = &[0x00];
+    let encoded = bracket!("0x00");
 
     let output = encode(&data);
     assert_eq!(output, encoded);
@@ -82,7 +83,7 @@ fn the_encoded_integer_0() {
 #[test]
 fn the_encoded_integer_15() {
     let data: &[u8] = &[0x0f];
-    let encoded = &[0x0f];
+    let encoded = bracket!("0x0f");
 
     let output = encode(&data);
     assert_eq!(output, encoded);
@@ -94,7 +95,7 @@ fn the_encoded_integer_15() {
 #[test]
 fn the_encoded_integer_1024() {
     let data: &[u8] = &[0x04, 0x00];
-    let encoded = &[0x82, 0x04, 0x00];
+    let encoded = bracket!("1024");
 
     let output = encode(&data);
     assert_eq!(output, encoded);
@@ -105,12 +106,11 @@ fn the_encoded_integer_1024() {
 
 #[test]
 fn the_set_theoretical_representation_of_three() {
-    // [ [], [[]], [ [], [[]] ] ]
     let element1 = MyVec(vec![]);
     let element2 = MyVec(vec![element1.clone()]);
     let element3 = MyVec(vec![element1.clone(), element2.clone()]);
     let data = vec![element1, element2, element3];
-    let encoded = [0xc7, 0xc0, 0xc1, 0xc0, 0xc3, 0xc0, 0xc1, 0xc0];
+    let encoded = bracket!("[ [], [[]], [ [], [[]] ] ]");
 
     let output = encode(&data);
     assert_eq!(output, encoded);
@@ -118,11 +118,11 @@ fn the_set_theoretical_representation_of_three() {
     let v: Vec<MyVec> = decode(&output).unwrap();
     assert_eq!(v, data);
 }
+
 #[test]
 fn the_string_lorem() {
     let data = b"Lorem ipsum dolor sit amet, consectetur adipisicing elit";
-    let mut encoded = vec![0xb8, 0x38];
-    encoded.extend(data);
+    let encoded = bracket!(r#""Lorem ipsum dolor sit amet, consectetur adipisicing elit""#);
 
     let output = encode(data);
     assert_eq!(output, encoded);
@@ -135,9 +135,13 @@ fn the_string_lorem() {
 struct MyVec(Vec<MyVec>);
 
 impl Encode for MyVec {
-    fn encode_to(&self, output: &mut Vec<u8>) {
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
         <&[MyVec] as Encode>::encode_to(&self.0.as_slice(), output);
     }
+
+    fn encoded_len(&self) -> usize {
+        <&[MyVec] as Encode>::encoded_len(&self.0.as_slice())
+    }
 }
 
 impl<'a> Decode<'a> for MyVec {
@@ -7,7 +7,10 @@
 //! Examples for struct RLP serialization.
 
 use hex_literal::hex;
-use rlp::{decode, encode, encode_to, Decode, Encode, Error, ItemPayloadSlice, ItemType};
+use rlp::{
+    decode, encode, encode_header, encode_to, encoded_header_len, encoded_len, Decode, Encode,
+    Error, ItemPayloadSlice, ItemType, Output,
+};
 
 #[derive(Debug, PartialEq)]
 struct Entry<'a> {
@@ -16,14 +19,23 @@ struct Entry<'a> {
     field2: Vec<u8>,
 }
 
+impl Entry<'_> {
+    fn payload_len(&self) -> usize {
+        encoded_len(&self.id) + encoded_len(&self.field1) + encoded_len(&self.field2)
+    }
+}
+
 impl Encode for Entry<'_> {
-    fn encode_to(&self, output: &mut Vec<u8>) {
-        let mut payload = vec![];
-        encode_to(&self.id, &mut payload);
-        encode_to(&self.field1, &mut payload);
-        encode_to(&self.field2, &mut payload);
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+        encode_header(ItemType::List, self.payload_len(), output);
+        encode_to(&self.id, output);
+        encode_to(&self.field1, output);
+        encode_to(&self.field2, output);
+    }
 
-        ItemPayloadSlice(&payload).encode_as_list(output);
+    fn encoded_len(&self) -> usize {
+        let payload_len = self.payload_len();
+        encoded_header_len(payload_len) + payload_len
     }
 }
 
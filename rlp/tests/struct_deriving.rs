@@ -5,7 +5,7 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use hex_literal::hex;
-use rlp::{decode, encode, Decode, Encode, Error};
+use rlp::{decode, decode_with_context, encode, Decode, Encode, Error, PathSegment};
 
 #[derive(Encode, Decode, Debug, PartialEq)]
 struct Entry<'a> {
@@ -67,3 +67,105 @@ fn test_vec_of_entry() {
     let vec_of_entry_decoded: Vec<Entry> = decode(&encoded).unwrap();
     assert_eq!(vec_of_entry_decoded, v);
 }
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Block<'a> {
+    number: u64,
+    entries: Vec<Entry<'a>>,
+}
+
+#[test]
+fn test_decoding_error_context() {
+    // [1, [[1, b"\x01\x02\x03", b"\x04\x05\x06"], [65536, b"\x01\x02\x03", b"\x04\x05\x06"]]]
+    let encoded = hex!("d901d7c9018301020383040506cc830100008301020383040506");
+
+    let err = decode_with_context::<Block>(&encoded).unwrap_err();
+    assert_eq!(err.error(), &Error::ItemPayloadByteLengthTooLarge);
+    assert_eq!(err.offset(), 14);
+    assert_eq!(
+        err.path(),
+        [
+            PathSegment::Field {
+                type_name: "Block",
+                name: "entries"
+            },
+            PathSegment::Index(1),
+            PathSegment::Field {
+                type_name: "Entry",
+                name: "id"
+            },
+        ]
+    );
+    assert_eq!(
+        err.to_string(),
+        "byte length of item payload exceeds decoding type capacity at byte 14, in Block.entries[1].id"
+    );
+
+    // The context is not recorded with `decode`.
+    assert_eq!(
+        decode::<Block>(&encoded).unwrap_err(),
+        Error::ItemPayloadByteLengthTooLarge
+    );
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Named<'buf> {
+    name: &'buf str,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Pair<'x, 'y> {
+    first: &'x [u8],
+    second: &'y str,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Tagged<'a, T, const N: usize>
+where
+    T: Clone,
+{
+    tag: [u8; N],
+    value: T,
+    note: &'a str,
+}
+
+#[test]
+fn test_generics() {
+    let named = Named { name: "cat" };
+    let encoded = encode(&named);
+    assert_eq!(encoded, hex!("c483636174"));
+    assert_eq!(decode::<Named>(&encoded).unwrap(), named);
+
+    let pair = Pair {
+        first: &[1, 2, 3],
+        second: "dog",
+    };
+    let encoded = encode(&pair);
+    assert_eq!(encoded, hex!("c88301020383646f67"));
+    assert_eq!(decode::<Pair>(&encoded).unwrap(), pair);
+
+    let tagged = Tagged {
+        tag: [0xaa, 0xbb],
+        value: vec![1_u64, 2],
+        note: "",
+    };
+    let encoded = encode(&tagged);
+    assert_eq!(encoded, hex!("c782aabbc2010280"));
+    assert_eq!(decode::<Tagged<Vec<u64>, 2>>(&encoded).unwrap(), tagged);
+}
+
+#[test]
+fn test_option_of_struct() {
+    let test_data = [
+        (Some(Named { name: "cat" }), &hex!("c483636174") as &[u8]),
+        // The empty list.
+        (None, &hex!("c0")),
+    ];
+
+    for (value, encoded) in test_data {
+        let output = encode(&value);
+        assert_eq!(output, encoded);
+
+        assert_eq!(decode::<Option<Named>>(&output).unwrap(), value);
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::net::{IpAddr, Ipv4Addr};
+
+use hex_literal::hex;
+use rlp::{decode, encode, Decode, Encode, Error};
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Endpoint(IpAddr, u16, u16);
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Empty;
+
+#[test]
+fn test_endpoint() {
+    let endpoint = Endpoint(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 30303, 30303);
+
+    let encoded = encode(&endpoint);
+    assert_eq!(&encoded, &hex!("cb847f00000182765f82765f"));
+
+    let endpoint_decoded: Endpoint = decode(&encoded).unwrap();
+    assert_eq!(endpoint_decoded, endpoint);
+}
+
+#[test]
+fn test_decoding_endpoint_errors() {
+    let data = [
+        // an extra item
+        (
+            &hex!("cc847f00000182765f82765f80") as &[u8],
+            Error::ListDecodingNumberDoesNotMatch,
+        ),
+        // the last item is missing
+        (
+            &hex!("c8847f00000182765f"),
+            Error::ListDecodingIterationEnded,
+        ),
+        // not a list
+        (&hex!("847f000001"), Error::ItemTypeDoesNotMatch),
+    ];
+    for (encoded, err) in data {
+        assert_eq!(decode::<Endpoint>(encoded).unwrap_err(), err);
+    }
+}
+
+#[test]
+fn test_vec_of_endpoint() {
+    let v = vec![
+        Endpoint(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 30303, 30303),
+        Endpoint(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 1, 2),
+    ];
+
+    let encoded = encode(&v);
+    assert_eq!(
+        &encoded,
+        &hex!("d4cb847f00000182765f82765fc7847f0000010102")
+    );
+
+    let vec_of_endpoint_decoded: Vec<Endpoint> = decode(&encoded).unwrap();
+    assert_eq!(vec_of_endpoint_decoded, v);
+}
+
+#[test]
+fn test_unit_struct() {
+    let encoded = encode(&Empty);
+    // the empty list
+    assert_eq!(&encoded, &hex!("c0"));
+
+    assert_eq!(decode::<Empty>(&encoded).unwrap(), Empty);
+    assert_eq!(
+        decode::<Empty>(&hex!("c180")).unwrap_err(),
+        Error::ListDecodingNumberDoesNotMatch
+    );
+}
@@ -0,0 +1,214 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Walks the items of RLP data, for the annotated hexdump and the validation.
+
+use std::fmt::Write;
+
+use rlp::{Error, ItemDataSlice, ItemType, Limit};
+
+/// The maximum nesting depth of lists walked.
+const MAX_DEPTH: usize = 1024;
+
+/// The maximum number of payload bytes shown for a single value.
+const MAX_PREVIEW_BYTE_LENGTH: usize = 16;
+
+/// An item of the data being walked.
+pub(crate) struct Item<'a> {
+    /// The offset of the item in the data.
+    pub(crate) offset: usize,
+    /// The nesting depth, 0 for the top-level item.
+    pub(crate) depth: usize,
+    pub(crate) item_type: ItemType,
+    /// The header, empty for a single byte encoding itself.
+    pub(crate) header: &'a [u8],
+    pub(crate) payload: &'a [u8],
+}
+
+/// The first violation found in the data, with its offset.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Violation {
+    pub(crate) offset: usize,
+    pub(crate) error: Error,
+}
+
+/// Walks the items of `data` depth-first, calling `visit` for each item,
+/// until the first item which isn't canonically encoded.
+///
+/// `data` must be exactly one item.
+pub(crate) fn walk<'a>(data: &'a [u8], visit: &mut dyn FnMut(Item<'a>)) -> Result<(), Violation> {
+    walk_item(data, data, 0, visit)
+}
+
+fn walk_item<'a>(
+    data: &'a [u8],
+    item_data: &'a [u8],
+    depth: usize,
+    visit: &mut dyn FnMut(Item<'a>),
+) -> Result<(), Violation> {
+    let offset = offset_in(data, item_data);
+    let (item_type, payload) = ItemDataSlice(item_data)
+        .as_payload()
+        .map_err(|error| Violation { offset, error })?;
+    visit(Item {
+        offset,
+        depth,
+        item_type,
+        header: &item_data[..item_data.len() - payload.0.len()],
+        payload: payload.0,
+    });
+
+    if item_type == ItemType::List {
+        if depth == MAX_DEPTH {
+            return Err(Violation {
+                offset,
+                error: Error::LimitExceeded(Limit::Depth),
+            });
+        }
+        let mut list_iter = payload.list_iter_unchecked();
+        loop {
+            let offset = offset_in(data, list_iter.remaining_list_payload().0);
+            match list_iter.next_itemdata() {
+                None => break,
+                Some(Err(error)) => return Err(Violation { offset, error }),
+                Some(Ok((_, _, item_data))) => walk_item(data, item_data.0, depth + 1, visit)?,
+            }
+        }
+    }
+    Ok(())
+}
+
+// Returns the offset of `sub`, a subslice of `data`.
+fn offset_in(data: &[u8], sub: &[u8]) -> usize {
+    sub.as_ptr() as usize - data.as_ptr() as usize
+}
+
+/// Returns the annotated hexdump of `data`: one line per item, with its
+/// offset, its header and (a preview of) its payload, indented by nesting
+/// depth, and what it is.
+///
+/// The lines of the items before the violation are returned along with it.
+pub(crate) fn dump(data: &[u8]) -> (String, Option<Violation>) {
+    let offset_width = format!("{:x}", data.len()).len().max(4);
+    let mut lines = vec![];
+    let result = walk(data, &mut |item| {
+        let mut bytes = hex::encode(item.header);
+        if item.item_type == ItemType::SingleValue && !item.payload.is_empty() {
+            if !bytes.is_empty() {
+                bytes.push(' ');
+            }
+            let preview_len = item.payload.len().min(MAX_PREVIEW_BYTE_LENGTH);
+            bytes.push_str(&hex::encode(&item.payload[..preview_len]));
+            if preview_len < item.payload.len() {
+                bytes.push_str("..");
+            }
+        }
+        let left = format!(
+            "{:0offset_width$x}: {}{bytes}",
+            item.offset,
+            "  ".repeat(item.depth)
+        );
+        lines.push((left, annotate(&item)));
+    });
+
+    let width = lines.iter().map(|(left, _)| left.len()).max().unwrap_or(0);
+    let mut s = String::new();
+    for (left, annotation) in lines {
+        writeln!(s, "{left:width$}  # {annotation}").unwrap();
+    }
+    (s, result.err())
+}
+
+fn annotate(item: &Item) -> String {
+    match item.item_type {
+        ItemType::List => format!("list, {} bytes", item.payload.len()),
+        ItemType::SingleValue if item.header.is_empty() => "byte".to_string(),
+        ItemType::SingleValue => {
+            let mut annotation = format!("string, {} bytes", item.payload.len());
+            if !item.payload.is_empty()
+                && item
+                    .payload
+                    .iter()
+                    .all(|b| b.is_ascii_graphic() || *b == b' ')
+            {
+                write!(annotation, " {:?}", String::from_utf8_lossy(item.payload)).unwrap();
+            }
+            annotation
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+    use rlp::RlpStream;
+
+    use super::*;
+
+    #[test]
+    fn test_dump() {
+        // [ "cat", [], [1, 0x0400] ]
+        let (s, violation) = dump(&hex!("ca 83636174 c0 c4 01 820400"));
+        assert_eq!(violation, None);
+        assert_eq!(
+            s,
+            "\
+0000: ca           # list, 10 bytes
+0001:   83 636174  # string, 3 bytes \"cat\"
+0005:   c0         # list, 0 bytes
+0006:   c4         # list, 4 bytes
+0007:     01       # byte
+0008:     82 0400  # string, 2 bytes
+"
+        );
+    }
+
+    #[test]
+    fn test_dump_long_payload() {
+        let mut data = vec![0xa0];
+        data.extend(0..32);
+        let (s, violation) = dump(&data);
+        assert_eq!(violation, None);
+        assert_eq!(
+            s,
+            "0000: a0 000102030405060708090a0b0c0d0e0f..  # string, 32 bytes\n"
+        );
+    }
+
+    #[test]
+    fn test_violations() {
+        let test_data = [
+            (&hex!("") as &[u8], 0, Error::EmptyData),
+            // [1, 0x00 encoded as two bytes]
+            (&hex!("c3 01 8100"), 2, Error::SingleByteEncodedAsTwo),
+            // [1, <truncated>]
+            (&hex!("c3 01 8300"), 2, Error::ItemDataWithInvalidByteLength),
+            // a trailing byte
+            (&hex!("c0 00"), 0, Error::ItemDataWithInvalidByteLength),
+        ];
+
+        for (data, offset, error) in test_data {
+            let (_, violation) = dump(data);
+            assert_eq!(violation, Some(Violation { offset, error }), "{data:x?}");
+        }
+
+        let mut stream = RlpStream::new();
+        for _ in 0..=MAX_DEPTH {
+            stream.begin_list();
+        }
+        for _ in 0..=MAX_DEPTH {
+            stream.end_list();
+        }
+        let nested = stream.out();
+        assert_eq!(
+            walk(&nested, &mut |_| {}).unwrap_err(),
+            Violation {
+                offset: nested.len() - 1,
+                error: Error::LimitExceeded(Limit::Depth)
+            }
+        );
+    }
+}
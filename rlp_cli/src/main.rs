@@ -0,0 +1,275 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! A command-line tool to decode, encode, dump and validate RLP.
+//!
+//! The RLP input is read from the hex argument (with an optional prefix
+//! `0x`), from the binary file given by `--file` (`-` for stdin), or else as
+//! hex from stdin. The text to encode is read from the argument, or else from
+//! stdin.
+//!
+//! # Examples
+//!
+//! ```sh
+//! # Decodes RLP data `[ [], [[]], [ [], [[]] ] ]` to JSON, or back to the
+//! # bracket notation.
+//! cargo run -p rlp_cli -- decode c7c0c1c0c3c0c1c0
+//! cargo run -p rlp_cli -- decode --bracket c7c0c1c0c3c0c1c0
+//!
+//! # Encodes to RLP.
+//! cargo run -p rlp_cli -- encode '["0x636174", ["0x01", "#1024"]]'
+//! cargo run -p rlp_cli -- encode --bracket '["cat", [1, 1024]]'
+//!
+//! # Prints the annotated hexdump of a binary file.
+//! cargo run -p rlp_cli -- dump --file block.rlp
+//!
+//! # Checks the encoding is canonical.
+//! echo 0xc3018100 | cargo run -p rlp_cli -- validate
+//! ```
+
+mod dump;
+
+use std::io::Read;
+use std::process::ExitCode;
+
+use rlp::{with_error_context, Stats};
+use rlp_types::{
+    bracket::{format_bracket_notation, parse_bracket_notation},
+    json::{decode_rlp_to_json_value, try_encode_json_value_to_rlp},
+};
+
+use crate::dump::{dump, Violation};
+
+const USAGE: &str = "\
+usage:
+    rlp decode [--bracket] [<HEX> | --file <PATH>]
+                                            decodes RLP to JSON, or the bracket notation
+    rlp encode [--bracket] [<TEXT>]         encodes JSON, or the bracket notation, to RLP
+    rlp dump [<HEX> | --file <PATH>]        prints the annotated hexdump of RLP
+    rlp validate [<HEX> | --file <PATH>]    checks RLP is canonically encoded
+
+Without <HEX> or <TEXT>, the input is read from stdin. `--file -` reads binary
+RLP from stdin.";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Decode { input: Input, bracket: bool },
+    Encode { text: Option<String>, bracket: bool },
+    Dump(Input),
+    Validate(Input),
+}
+
+/// Where the RLP input is read from.
+#[derive(Debug, PartialEq)]
+enum Input {
+    Hex(String),
+    /// A binary file, `-` for stdin.
+    File(String),
+    /// Hex from stdin.
+    Stdin,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let command = args.next().ok_or("the command is missing")?;
+    let mut positional = None;
+    let mut file = None;
+    let mut bracket = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--file" if command != "encode" => {
+                file = Some(args.next().ok_or("the path of --file is missing")?);
+            }
+            "--bracket" if command == "encode" || command == "decode" => bracket = true,
+            _ if arg.starts_with("--") => return Err(format!("unknown option {arg}")),
+            _ if positional.is_none() => positional = Some(arg),
+            _ => return Err(format!("unexpected argument {arg}")),
+        }
+    }
+
+    let input = match (positional, file) {
+        (Some(_), Some(_)) if command != "encode" => {
+            return Err("both <HEX> and --file are given".to_string())
+        }
+        (Some(hex), None) => Input::Hex(hex),
+        (None, Some(path)) => Input::File(path),
+        _ => Input::Stdin,
+    };
+    match command.as_str() {
+        "decode" => Ok(Command::Decode { input, bracket }),
+        "encode" => Ok(Command::Encode {
+            text: match input {
+                Input::Hex(text) => Some(text),
+                _ => None,
+            },
+            bracket,
+        }),
+        "dump" => Ok(Command::Dump(input)),
+        "validate" => Ok(Command::Validate(input)),
+        _ => Err(format!("unknown command {command}")),
+    }
+}
+
+fn read_stdin_to_string() -> Result<String, String> {
+    let mut s = String::new();
+    std::io::stdin()
+        .read_to_string(&mut s)
+        .map_err(|e| format!("reading stdin failed: {e}"))?;
+    Ok(s)
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, String> {
+    let s = s.trim();
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    hex::decode(s).map_err(|e| format!("invalid hex input: {e}"))
+}
+
+fn read_input(input: &Input) -> Result<Vec<u8>, String> {
+    match input {
+        Input::Hex(s) => decode_hex(s),
+        Input::File(path) if path == "-" => {
+            let mut data = vec![];
+            std::io::stdin()
+                .read_to_end(&mut data)
+                .map_err(|e| format!("reading stdin failed: {e}"))?;
+            Ok(data)
+        }
+        Input::File(path) => std::fs::read(path).map_err(|e| format!("reading {path} failed: {e}")),
+        Input::Stdin => decode_hex(&read_stdin_to_string()?),
+    }
+}
+
+fn format_violation(violation: &Violation) -> String {
+    format!(
+        "invalid RLP at byte {}: {}",
+        violation.offset, violation.error
+    )
+}
+
+fn validate(data: &[u8]) -> Result<Stats, String> {
+    with_error_context(data, || rlp::validate(data)).map_err(|e| format!("invalid RLP: {e}"))
+}
+
+fn run(command: Command) -> Result<(), String> {
+    match command {
+        Command::Decode { input, bracket } => {
+            let data = read_input(&input)?;
+            // Reports where the data isn't canonically encoded, if so.
+            validate(&data)?;
+            if bracket {
+                let s =
+                    format_bracket_notation(&data).map_err(|e| format!("decoding failed: {e}"))?;
+                println!("{s}");
+            } else {
+                let value =
+                    decode_rlp_to_json_value(&data).map_err(|e| format!("decoding failed: {e}"))?;
+                println!("{}", serde_json::to_string_pretty(&value).unwrap());
+            }
+        }
+        Command::Encode { text, bracket } => {
+            let text = match text {
+                Some(text) => text,
+                None => read_stdin_to_string()?,
+            };
+            let data = if bracket {
+                parse_bracket_notation(&text)
+                    .map_err(|e| format!("invalid bracket notation: {e}"))?
+            } else {
+                let value: serde_json::Value =
+                    serde_json::from_str(&text).map_err(|e| format!("invalid JSON: {e}"))?;
+                try_encode_json_value_to_rlp(&value).map_err(|e| format!("encoding failed: {e}"))?
+            };
+            println!("{}", hex::encode(data));
+        }
+        Command::Dump(input) => {
+            let data = read_input(&input)?;
+            let (s, violation) = dump(&data);
+            print!("{s}");
+            if let Some(violation) = violation {
+                return Err(format_violation(&violation));
+            }
+        }
+        Command::Validate(input) => {
+            let data = read_input(&input)?;
+            let stats = validate(&data)?;
+            println!(
+                "valid RLP: {} items, depth {}, largest payload {} bytes",
+                stats.item_count, stats.max_depth, stats.max_payload_byte_length
+            );
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let command = match parse_args(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("error: {e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    match run(command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            parse(&["decode", "0xc0"]),
+            Ok(Command::Decode {
+                input: Input::Hex("0xc0".to_string()),
+                bracket: false
+            })
+        );
+        assert_eq!(
+            parse(&["decode", "--bracket", "--file", "a.rlp"]),
+            Ok(Command::Decode {
+                input: Input::File("a.rlp".to_string()),
+                bracket: true
+            })
+        );
+        assert_eq!(
+            parse(&["dump", "--file", "-"]),
+            Ok(Command::Dump(Input::File("-".to_string())))
+        );
+        assert_eq!(parse(&["validate"]), Ok(Command::Validate(Input::Stdin)));
+        assert_eq!(
+            parse(&["encode", "--bracket", "[]"]),
+            Ok(Command::Encode {
+                text: Some("[]".to_string()),
+                bracket: true
+            })
+        );
+        assert_eq!(
+            parse(&["encode"]),
+            Ok(Command::Encode {
+                text: None,
+                bracket: false
+            })
+        );
+
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["convert"]).is_err());
+        assert!(parse(&["decode", "c0", "c0"]).is_err());
+        assert!(parse(&["decode", "c0", "--file", "a.rlp"]).is_err());
+        assert!(parse(&["decode", "--file"]).is_err());
+        assert!(parse(&["dump", "--bracket"]).is_err());
+        assert!(parse(&["encode", "--file", "a.json"]).is_err());
+    }
+}
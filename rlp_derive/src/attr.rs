@@ -0,0 +1,185 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Parses the helper attribute `#[rlp(...)]`.
+
+use syn::{Attribute, Field, Fields, LitInt, LitStr, Path};
+
+/// How the variants of an enum are represented in RLP.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum EnumRepr {
+    /// `[tag, value]`, a list of the tag and the variant value.
+    ///
+    /// The value is omitted for unit variants.
+    List,
+    /// `tag || rlp(value)` wrapped as a RLP string.
+    ///
+    /// The tag byte is prepended to the RLP encoding of the variant value,
+    /// like the message type prefix of discv5 or the typed transaction
+    /// envelope of EIP-2718.
+    Prefix,
+    /// The variant value only, decoded by trying each variant in order.
+    Untagged,
+}
+
+/// Attributes applied to an enum.
+pub(crate) struct EnumAttrs {
+    pub(crate) repr: EnumRepr,
+}
+
+/// Attributes applied to an enum variant.
+pub(crate) struct VariantAttrs {
+    pub(crate) tag: Option<u8>,
+}
+
+/// Attributes applied to a field encoded as an item of a list.
+#[derive(Default)]
+pub(crate) struct FieldAttrs {
+    /// `#[rlp(skip)]`, the field is neither encoded nor decoded, and is
+    /// initialized with `Default::default()` when decoding.
+    pub(crate) skip: bool,
+    /// `#[rlp(default)]`, the field is set to `Default::default()` if the list
+    /// ends before it when decoding.
+    pub(crate) default: bool,
+    /// `#[rlp(trailing)]`, the field is an `Option`, which is decoded as
+    /// `None` if the list ends before it, and ends the list when it is `None`
+    /// when encoding.
+    pub(crate) trailing: bool,
+    /// `#[rlp(with = "module")]`, the field is encoded with
+    /// `module::encode_to` and `module::encoded_len`, and decoded with
+    /// `module::decode`.
+    ///
+    /// The functions have the same signatures as `rlp::encode_to`,
+    /// `rlp::encoded_len` and `rlp::Decode::decode_item`.
+    pub(crate) with: Option<Path>,
+}
+
+impl FieldAttrs {
+    /// Returns true if the field can be absent at the end of a list.
+    pub(crate) fn is_optional(&self) -> bool {
+        self.default || self.trailing
+    }
+}
+
+/// Parses the attributes of an enum, e.g. `#[rlp(repr = "prefix")]`.
+pub(crate) fn parse_enum_attrs(attrs: &[Attribute]) -> syn::Result<EnumAttrs> {
+    let mut repr = EnumRepr::List;
+    for attr in rlp_attrs(attrs) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("repr") {
+                let value: LitStr = meta.value()?.parse()?;
+                repr = match value.value().as_str() {
+                    "list" => EnumRepr::List,
+                    "prefix" => EnumRepr::Prefix,
+                    "untagged" => EnumRepr::Untagged,
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            value,
+                            r#"expected "list", "prefix" or "untagged""#,
+                        ))
+                    }
+                };
+                Ok(())
+            } else {
+                Err(meta.error("unsupported rlp enum attribute"))
+            }
+        })?;
+    }
+    Ok(EnumAttrs { repr })
+}
+
+/// Parses the attributes of an enum variant, e.g. `#[rlp(tag = 0x01)]`.
+pub(crate) fn parse_variant_attrs(attrs: &[Attribute]) -> syn::Result<VariantAttrs> {
+    let mut tag = None;
+    for attr in rlp_attrs(attrs) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("tag") {
+                let value: LitInt = meta.value()?.parse()?;
+                tag = Some(value.base10_parse::<u8>()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported rlp variant attribute"))
+            }
+        })?;
+    }
+    Ok(VariantAttrs { tag })
+}
+
+/// Parses the attributes of `fields` which are encoded as the items of a list.
+///
+/// Checks the attributes don't conflict, and the fields which can be absent
+/// are placed after all the others.
+pub(crate) fn parse_list_field_attrs(fields: &Fields) -> syn::Result<Vec<FieldAttrs>> {
+    let mut all_attrs: Vec<FieldAttrs> = vec![];
+    for field in fields {
+        let attrs = parse_field_attrs(field)?;
+        if attrs.skip && (attrs.is_optional() || attrs.with.is_some()) {
+            return Err(syn::Error::new_spanned(
+                field,
+                "`skip` cannot be combined with other rlp attributes",
+            ));
+        }
+        if attrs.default && attrs.trailing {
+            return Err(syn::Error::new_spanned(
+                field,
+                "`default` cannot be combined with `trailing`",
+            ));
+        }
+        if !attrs.skip && !attrs.is_optional() && all_attrs.iter().any(|attrs| attrs.is_optional())
+        {
+            return Err(syn::Error::new_spanned(
+                field,
+                "field must be `default` or `trailing` after a `default` or `trailing` field",
+            ));
+        }
+        all_attrs.push(attrs);
+    }
+    Ok(all_attrs)
+}
+
+/// Checks `field` has no `#[rlp(...)]` attributes.
+///
+/// For the field of a "newtype", which is encoded as it is.
+pub(crate) fn check_no_field_attrs(field: &Field) -> syn::Result<()> {
+    match rlp_attrs(&field.attrs).next() {
+        Some(attr) => Err(syn::Error::new_spanned(
+            attr,
+            "rlp field attributes are not supported for \"newtype\"",
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Parses the attributes of a field, e.g. `#[rlp(trailing)]`.
+fn parse_field_attrs(field: &Field) -> syn::Result<FieldAttrs> {
+    let mut field_attrs = FieldAttrs::default();
+    for attr in rlp_attrs(&field.attrs) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                field_attrs.skip = true;
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                field_attrs.default = true;
+                Ok(())
+            } else if meta.path.is_ident("trailing") {
+                field_attrs.trailing = true;
+                Ok(())
+            } else if meta.path.is_ident("with") {
+                let value: LitStr = meta.value()?.parse()?;
+                field_attrs.with = Some(value.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported rlp field attribute"))
+            }
+        })?;
+    }
+    Ok(field_attrs)
+}
+
+/// Returns the attributes with the path `rlp`.
+fn rlp_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("rlp"))
+}
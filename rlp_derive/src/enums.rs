@@ -0,0 +1,503 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Generates the RLP encoding and decoding of enums.
+//!
+//! See `EnumRepr` for the supported representations.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DataEnum, Fields, Ident, Variant};
+
+use crate::attr::{check_no_field_attrs, parse_variant_attrs, EnumAttrs, EnumRepr};
+use crate::{construct, decode_list_fields, encode_list_fields, field_bindings};
+
+// Generate expressions to encode the variant of `self` and append the result
+// to `output`, and an expression of the length in bytes of the encoding.
+pub(crate) fn encode_enum_variants(
+    data: &DataEnum,
+    attrs: &EnumAttrs,
+) -> syn::Result<(TokenStream, TokenStream)> {
+    let tags = variant_tags(data, attrs)?;
+
+    let mut encoding_arms = vec![];
+    let mut encoded_len_arms = vec![];
+    for (index, variant) in data.variants.iter().enumerate() {
+        let ident = &variant.ident;
+        let bindings = field_bindings(&variant.fields);
+        let pattern = construct(&quote!(Self::#ident), &variant.fields, &bindings);
+        let (value, value_len) = encode_variant_value(&variant.fields, &bindings)?;
+
+        let (encoding, encoded_len) = match attrs.repr {
+            // Expands to expressions like
+            //
+            // ```
+            // Self::Variant(f0) => {
+            //     let payload_len = 1 + rlp::encoded_len(f0);
+            //     rlp::encode_header(rlp::ItemType::List, payload_len, output);
+            //     rlp::encode_to(&rlp::U8(1), output);
+            //     rlp::encode_to(f0, output);
+            // }
+            // ```
+            EnumRepr::List => {
+                let tag = tags[index];
+                let tag_len = rlp_encoded_tag_len(tag);
+                (
+                    quote! {
+                        let payload_len = #tag_len + #value_len;
+                        rlp::encode_header(rlp::ItemType::List, payload_len, output);
+                        rlp::encode_to(&rlp::U8(#tag), output);
+                        #value
+                    },
+                    quote! {
+                        let payload_len = #tag_len + #value_len;
+                        rlp::encoded_header_len(payload_len) + payload_len
+                    },
+                )
+            }
+            // The payload is the tag byte followed by the value.
+            EnumRepr::Prefix => {
+                let tag = tags[index];
+                if variant.fields.is_empty() {
+                    // The tag byte alone, which can be its own RLP encoding.
+                    let payload = quote!(rlp::ItemPayloadSlice(&[#tag]));
+                    (
+                        quote!(#payload.encode_as_single_value(output);),
+                        quote!(#payload.encoded_len_as_single_value()),
+                    )
+                } else {
+                    (
+                        quote! {
+                            let payload_len = 1 + #value_len;
+                            rlp::encode_header(rlp::ItemType::SingleValue, payload_len, output);
+                            output.push(#tag);
+                            #value
+                        },
+                        quote! {
+                            let payload_len = 1 + #value_len;
+                            rlp::encoded_header_len(payload_len) + payload_len
+                        },
+                    )
+                }
+            }
+            EnumRepr::Untagged => (value, value_len),
+        };
+
+        encoding_arms.push(quote! {
+            #[allow(unused_variables)]
+            #pattern => {
+                #encoding
+            }
+        });
+        encoded_len_arms.push(quote! {
+            #[allow(unused_variables)]
+            #pattern => {
+                #encoded_len
+            }
+        });
+    }
+
+    Ok((
+        quote! {
+            match self {
+                #(#encoding_arms)*
+            }
+        },
+        quote! {
+            match self {
+                #(#encoded_len_arms)*
+            }
+        },
+    ))
+}
+
+// Generate an expression of the item type the enum is encoded as, `None` for
+// an untagged enum without variants.
+pub(crate) fn encoding_enum_item_type(data: &DataEnum, attrs: &EnumAttrs) -> Option<TokenStream> {
+    match attrs.repr {
+        EnumRepr::List => Some(quote!(rlp::ItemType::List)),
+        EnumRepr::Prefix => Some(quote!(rlp::ItemType::SingleValue)),
+        // The item type of the first variant, like `Decode::TYPE`.
+        EnumRepr::Untagged => data.variants.first().map(|variant| match variant.fields {
+            Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+                let ty = &fields.unnamed.first().unwrap().ty;
+                quote!(<#ty as rlp::Encode>::encoded_type())
+            }
+            _ => quote!(rlp::ItemType::List),
+        }),
+    }
+}
+
+// Generate expressions to decode the variant and create a new `Self`.
+pub(crate) fn decode_enum_variants(
+    name: &Ident,
+    data: &DataEnum,
+    attrs: &EnumAttrs,
+) -> syn::Result<TokenStream> {
+    let tags = variant_tags(data, attrs)?;
+
+    let decoding = match attrs.repr {
+        EnumRepr::List => {
+            let arms = data.variants.iter().zip(tags).map(|(variant, tag)| {
+                let value = decode_variant_value_from_list_iter(name, variant)?;
+                Ok(quote! {
+                    #tag => #value,
+                })
+            });
+            let arms = arms.collect::<syn::Result<Vec<_>>>()?;
+            quote! {
+                let mut list_iter = payload.list_iter_unchecked();
+                let tag: rlp::U8 = list_iter.next_item()?;
+                let value = match tag.0 {
+                    #(#arms)*
+                    _ => return Err(rlp::Error::UnknownEnumVariantTag),
+                };
+
+                if list_iter.next().is_some() {
+                    return Err(rlp::Error::ListDecodingNumberDoesNotMatch);
+                }
+
+                Ok(value)
+            }
+        }
+        EnumRepr::Prefix => {
+            let arms = data.variants.iter().zip(tags).map(|(variant, tag)| {
+                let value = decode_variant_value_from_item_data(name, variant)?;
+                Ok(quote! {
+                    #tag => #value,
+                })
+            });
+            let arms = arms.collect::<syn::Result<Vec<_>>>()?;
+            quote! {
+                let (tag, data) = payload.0.split_first().ok_or(rlp::Error::EmptyData)?;
+                let value = match *tag {
+                    #(#arms)*
+                    _ => return Err(rlp::Error::UnknownEnumVariantTag),
+                };
+
+                Ok(value)
+            }
+        }
+        EnumRepr::Untagged => {
+            // Expands to expressions like
+            //
+            // ```
+            // let result = (|| -> Result<Self, rlp::Error> {
+            //     Ok(Self::Variant(rlp::decode_payload(Self::TYPE, payload)?))
+            // })();
+            // if let Ok(value) = result {
+            //     return Ok(value);
+            // }
+            // ```
+            //
+            // for each variant in order.
+            let attempts = decode_untagged_variants(name, data, false)?;
+            quote! {
+                #(#attempts)*
+
+                Err(rlp::Error::NoEnumVariantMatched)
+            }
+        }
+    };
+
+    Ok(decoding)
+}
+
+// Generate expressions to decode the variant from the whole item and create a
+// new `Self`, `None` if decoding the payload is enough.
+pub(crate) fn decode_enum_item(
+    name: &Ident,
+    data: &DataEnum,
+    attrs: &EnumAttrs,
+) -> syn::Result<Option<TokenStream>> {
+    match attrs.repr {
+        EnumRepr::List | EnumRepr::Prefix => Ok(None),
+        EnumRepr::Untagged => {
+            // Like `decode`, except that the variants are tried with the item
+            // type of the item.
+            let attempts = decode_untagged_variants(name, data, true)?;
+            Ok(Some(quote! {
+                #(#attempts)*
+
+                if item_type != Self::TYPE {
+                    return Err(rlp::Error::ItemTypeDoesNotMatch);
+                }
+                Err(rlp::Error::NoEnumVariantMatched)
+            }))
+        }
+    }
+}
+
+// Generate statements trying to decode each variant of an untagged enum in
+// order, and returning the first one decoded.
+fn decode_untagged_variants(
+    name: &Ident,
+    data: &DataEnum,
+    from_item: bool,
+) -> syn::Result<Vec<TokenStream>> {
+    data.variants
+        .iter()
+        .map(|variant| {
+            let value = decode_variant_value_from_payload(name, variant, from_item)?;
+            Ok(quote! {
+                let result = (|| -> Result<Self, rlp::Error> {
+                    Ok(#value)
+                })();
+                if let Ok(value) = result {
+                    return Ok(value);
+                }
+            })
+        })
+        .collect()
+}
+
+// Generate item type
+pub(crate) fn decoding_enum_item_type(
+    data: &DataEnum,
+    attrs: &EnumAttrs,
+) -> syn::Result<TokenStream> {
+    match attrs.repr {
+        EnumRepr::List => Ok(quote! {
+            rlp::ItemType::List
+        }),
+        EnumRepr::Prefix => Ok(quote! {
+            rlp::ItemType::SingleValue
+        }),
+        EnumRepr::Untagged => {
+            // The item type of the first variant. The variants of other types
+            // are only matched when decoding from the whole item, e.g. with
+            // `rlp::decode`, not from the payload alone.
+            let variant = data.variants.first().ok_or_else(|| {
+                syn::Error::new(
+                    data.enum_token.span,
+                    "untagged enum must have at least one variant",
+                )
+            })?;
+            match variant.fields {
+                Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+                    let ty = &fields.unnamed.first().unwrap().ty;
+                    Ok(quote! {
+                        <#ty as rlp::Decode>::TYPE
+                    })
+                }
+                _ => Ok(quote! {
+                    rlp::ItemType::List
+                }),
+            }
+        }
+    }
+}
+
+// Returns the tags of the variants.
+//
+// A tag is specified with `#[rlp(tag = N)]`, or defaults to the index of the
+// variant. Returns an empty `Vec` for an untagged enum.
+fn variant_tags(data: &DataEnum, attrs: &EnumAttrs) -> syn::Result<Vec<u8>> {
+    let mut tags: Vec<u8> = vec![];
+    for (index, variant) in data.variants.iter().enumerate() {
+        let variant_attrs = parse_variant_attrs(&variant.attrs)?;
+
+        if attrs.repr == EnumRepr::Untagged {
+            if variant_attrs.tag.is_some() {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "untagged enum variant cannot have a tag",
+                ));
+            }
+            if variant.fields.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "untagged enum variant must have at least one field",
+                ));
+            }
+            continue;
+        }
+
+        let tag = match variant_attrs.tag {
+            Some(tag) => tag,
+            None => u8::try_from(index).map_err(|_| {
+                syn::Error::new_spanned(variant, "variant index exceeds the maximum tag 255")
+            })?,
+        };
+        if tags.contains(&tag) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                format!("duplicate rlp tag {tag}"),
+            ));
+        }
+        tags.push(tag);
+    }
+    Ok(tags)
+}
+
+// Generate expressions to encode the value of a variant and append the result
+// to `output`, and an expression of the length in bytes of the encoding.
+//
+// Encodes nothing for a unit variant, the field as it is for a "newtype"
+// variant, and a list of the fields for others.
+fn encode_variant_value(
+    fields: &Fields,
+    bindings: &[syn::Ident],
+) -> syn::Result<(TokenStream, TokenStream)> {
+    match fields {
+        Fields::Unit => Ok((quote! {}, quote!(0))),
+        Fields::Unnamed(ref unnamed) if unnamed.unnamed.len() == 1 => {
+            check_no_field_attrs(unnamed.unnamed.first().unwrap())?;
+            let binding = &bindings[0];
+            Ok((
+                quote! {
+                    rlp::encode_to(#binding, output);
+                },
+                quote! {
+                    rlp::encoded_len(#binding)
+                },
+            ))
+        }
+        _ => {
+            let values = bindings.iter().map(|binding| quote!(#binding));
+            encode_list_fields(fields, values, quote!(output))
+        }
+    }
+}
+
+// Returns the length in bytes of the encoding of `rlp::U8(tag)`.
+fn rlp_encoded_tag_len(tag: u8) -> usize {
+    match tag {
+        // The integer 0 is encoded as the empty string.
+        0..=0x7f => 1,
+        _ => 2,
+    }
+}
+
+// Generate an expression to decode the value of `variant` from the remaining
+// items of `list_iter`.
+fn decode_variant_value_from_list_iter(
+    name: &Ident,
+    variant: &Variant,
+) -> syn::Result<TokenStream> {
+    let ident = &variant.ident;
+    match variant.fields {
+        Fields::Unit => Ok(quote! {
+            Self::#ident
+        }),
+        Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+            check_no_field_attrs(fields.unnamed.first().unwrap())?;
+            Ok(quote! {
+                Self::#ident(list_iter.next_item()?)
+            })
+        }
+        _ => {
+            let type_name = format!("{name}::{ident}");
+            let decoding = decode_list_fields(
+                &quote!(Self::#ident),
+                &type_name,
+                &variant.fields,
+                quote!(item_payload),
+            )?;
+            Ok(quote! {
+                {
+                    let (item_type, item_payload) = match list_iter.next() {
+                        Some(result) => result?,
+                        None => return Err(rlp::Error::ListDecodingIterationEnded),
+                    };
+                    if item_type != rlp::ItemType::List {
+                        return Err(rlp::Error::ItemTypeDoesNotMatch);
+                    }
+                    #decoding
+                }
+            })
+        }
+    }
+}
+
+// Generate an expression to decode the value of `variant` from the item
+// `data` following the tag byte.
+fn decode_variant_value_from_item_data(
+    name: &Ident,
+    variant: &Variant,
+) -> syn::Result<TokenStream> {
+    let ident = &variant.ident;
+    match variant.fields {
+        Fields::Unit => Ok(quote! {
+            {
+                if !data.is_empty() {
+                    return Err(rlp::Error::ItemDataWithInvalidByteLength);
+                }
+                Self::#ident
+            }
+        }),
+        Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+            check_no_field_attrs(fields.unnamed.first().unwrap())?;
+            Ok(quote! {
+                Self::#ident(rlp::decode(data)?)
+            })
+        }
+        _ => {
+            let type_name = format!("{name}::{ident}");
+            let decoding = decode_list_fields(
+                &quote!(Self::#ident),
+                &type_name,
+                &variant.fields,
+                quote!(item_payload),
+            )?;
+            Ok(quote! {
+                {
+                    let (item_type, item_payload) = rlp::ItemDataSlice(data).as_payload()?;
+                    if item_type != rlp::ItemType::List {
+                        return Err(rlp::Error::ItemTypeDoesNotMatch);
+                    }
+                    #decoding
+                }
+            })
+        }
+    }
+}
+
+// Generate an expression to decode the value of `variant` from the whole
+// `payload`, of the item `item_data` of `item_type` if `from_item`, else of
+// `Self::TYPE`.
+fn decode_variant_value_from_payload(
+    name: &Ident,
+    variant: &Variant,
+    from_item: bool,
+) -> syn::Result<TokenStream> {
+    let ident = &variant.ident;
+    let item_type = if from_item {
+        quote!(item_type)
+    } else {
+        quote!(Self::TYPE)
+    };
+    match variant.fields {
+        Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+            check_no_field_attrs(fields.unnamed.first().unwrap())?;
+            let value = if from_item {
+                quote!(rlp::Decode::decode_item(item_type, item_data, payload)?)
+            } else {
+                quote!(rlp::decode_payload(Self::TYPE, payload)?)
+            };
+            Ok(quote! {
+                Self::#ident(#value)
+            })
+        }
+        _ => {
+            let type_name = format!("{name}::{ident}");
+            let decoding = decode_list_fields(
+                &quote!(Self::#ident),
+                &type_name,
+                &variant.fields,
+                quote!(payload),
+            )?;
+            Ok(quote! {
+                {
+                    if #item_type != rlp::ItemType::List {
+                        return Err(rlp::Error::ItemTypeDoesNotMatch);
+                    }
+                    #decoding
+                }
+            })
+        }
+    }
+}
@@ -5,15 +5,74 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 //! Implements the derive macros for the trait `rlp::Encode` and `rlp::Decode`.
+//!
+//! # Structs
+//!
+//! A struct with named fields, a tuple struct, or a unit struct is represented
+//! as a list of its fields, while a "newtype" is represented as its only field.
+//!
+//! All the fields are decoded from their whole items with
+//! `rlp::Decode::decode_item`, so the types keeping the items as they are,
+//! e.g. `rlp::RlpRaw`, can be fields in any position.
+//!
+//! The fields represented as list items accept the attributes:
+//!
+//! - `#[rlp(skip)]`: the field is not encoded, and is set to
+//!   `Default::default()` when decoding.
+//! - `#[rlp(default)]`: the field is set to `Default::default()` if the list
+//!   ends before it.
+//! - `#[rlp(trailing)]`: the `Option` field is set to `None` if the list ends
+//!   before it. When encoding, the list ends at the first `None`.
+//! - `#[rlp(with = "module")]`: the field is encoded with `module::encode_to`
+//!   and `module::encoded_len`, and decoded with `module::decode`, which have
+//!   the same signatures as `rlp::encode_to`, `rlp::encoded_len` and
+//!   `rlp::Decode::decode_item`.
+//!
+//! The `default` and `trailing` fields must follow all the other fields.
+//!
+//! # Enums
+//!
+//! The representation of an enum is selected with `#[rlp(repr = "...")]`:
+//!
+//! - `"list"` (default): `[tag, value]`, the value is omitted for unit
+//!   variants.
+//! - `"prefix"`: a RLP string of the tag byte followed by the RLP encoding of
+//!   the value, e.g. the typed transaction envelope of EIP-2718.
+//! - `"untagged"`: the value only, decoded by trying each variant in order.
+//!
+//! The tag of a variant is specified with `#[rlp(tag = N)]`, or defaults to
+//! the index of the variant. The value of a "newtype" variant is its field,
+//! the value of other variants is a list of their fields.
+//!
+//! # Generics
+//!
+//! The type parameters are bound to implement `rlp::Encode`, or
+//! `rlp::Decode<'de>` where `'de` is the lifetime of the decoding data. The
+//! fields borrowing the decoding data may use any lifetimes of the type, which
+//! are all required to be `'de` when decoding. Const parameters and
+//! where-clauses are kept as they are.
+//!
+//! Unions are not supported.
 
-use proc_macro2::TokenStream;
-use quote::{quote, quote_spanned};
+mod attr;
+mod enums;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::ext::IdentExt;
 use syn::spanned::Spanned;
 use syn::{
-    parse_macro_input, parse_quote, Data, DeriveInput, Fields, GenericParam, Generics, Ident,
+    parse_macro_input, parse_quote, Data, DataStruct, DeriveInput, Field, Fields, GenericParam,
+    Generics, Ident, Index, Lifetime, Path,
+};
+
+use attr::{check_no_field_attrs, parse_enum_attrs, parse_list_field_attrs, FieldAttrs};
+use enums::{
+    decode_enum_item, decode_enum_variants, decoding_enum_item_type, encode_enum_variants,
+    encoding_enum_item_type,
 };
 
-#[proc_macro_derive(Encode)]
+#[proc_macro_derive(Encode, attributes(rlp))]
 pub fn derive_encode(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     // Parse the input tokens into a syntax tree.
     let input = parse_macro_input!(input as DeriveInput);
@@ -24,14 +83,43 @@ pub fn derive_encode(input: proc_macro::TokenStream) -> proc_macro::TokenStream
     let generics = add_trait_encode_bounds(input.generics);
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let encoding = encode_struct_fields(&input.data);
+    let result = match input.data {
+        Data::Struct(ref data) => encode_struct_fields(data).map(|(encoding, encoded_len)| {
+            (encoding, encoded_len, Some(encoding_struct_item_type(data)))
+        }),
+        Data::Enum(ref data) => parse_enum_attrs(&input.attrs).and_then(|attrs| {
+            let (encoding, encoded_len) = encode_enum_variants(data, &attrs)?;
+            Ok((encoding, encoded_len, encoding_enum_item_type(data, &attrs)))
+        }),
+        Data::Union(ref data) => Err(syn::Error::new_spanned(
+            data.union_token,
+            "rlp::Encode cannot be derived for unions",
+        )),
+    };
+    let (encoding, encoded_len, item_type) = match result {
+        Ok(encoding) => encoding,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let encoded_type = item_type.map(|item_type| {
+        quote! {
+            fn encoded_type() -> rlp::ItemType {
+                #item_type
+            }
+        }
+    });
 
     let expanded = quote! {
         // The generated impl.
         impl #impl_generics rlp::Encode for #name #ty_generics #where_clause {
-            fn encode_to(&self, output: &mut Vec<u8>) {
+            fn encode_to<O: rlp::Output + ?Sized>(&self, output: &mut O) {
                 #encoding
             }
+
+            fn encoded_len(&self) -> usize {
+                #encoded_len
+            }
+
+            #encoded_type
         }
     };
 
@@ -39,7 +127,7 @@ pub fn derive_encode(input: proc_macro::TokenStream) -> proc_macro::TokenStream
     proc_macro::TokenStream::from(expanded)
 }
 
-#[proc_macro_derive(Decode)]
+#[proc_macro_derive(Decode, attributes(rlp))]
 pub fn derive_decode(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     // Parse the input tokens into a syntax tree.
     let input = parse_macro_input!(input as DeriveInput);
@@ -47,21 +135,62 @@ pub fn derive_decode(input: proc_macro::TokenStream) -> proc_macro::TokenStream
     // Used in the quasi-quotation below as `#name`.
     let name = input.ident;
 
-    let generics = add_trait_decode_bounds(input.generics);
-    let (_, ty_generics, where_clause) = generics.split_for_impl();
+    // The impl is generic over the lifetime `de` of the decoding data.
+    let (impl_generics_with_de, de) = add_decoding_lifetime(&input.generics);
+    let impl_generics_with_de = add_trait_decode_bounds(impl_generics_with_de, &de);
+    let (impl_generics, _, where_clause) = impl_generics_with_de.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
 
-    let item_type = decoding_struct_item_type(&input.data);
-    let decoding = decode_struct_fields(&name, &input.data);
+    let result = match input.data {
+        Data::Struct(ref data) => decode_struct_fields(&name, data).map(|decoding| {
+            (
+                decoding_struct_item_type(data),
+                decoding,
+                decode_struct_item(&name, data),
+            )
+        }),
+        Data::Enum(ref data) => parse_enum_attrs(&input.attrs).and_then(|attrs| {
+            Ok((
+                decoding_enum_item_type(data, &attrs)?,
+                decode_enum_variants(&name, data, &attrs)?,
+                decode_enum_item(&name, data, &attrs)?,
+            ))
+        }),
+        Data::Union(ref data) => Err(syn::Error::new_spanned(
+            data.union_token,
+            "rlp::Decode cannot be derived for unions",
+        )),
+    };
+    let (item_type, decoding, item_decoding) = match result {
+        Ok(result) => result,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    // Overrides `decode_item` to pass the whole item to the decoding of the
+    // value, if any.
+    let decode_item = item_decoding.map(|item_decoding| {
+        quote! {
+            #[allow(unused_variables)]
+            fn decode_item(
+                item_type: rlp::ItemType,
+                item_data: rlp::ItemDataSlice<#de>,
+                payload: rlp::ItemPayloadSlice<#de>,
+            ) -> Result<Self, rlp::Error> {
+                #item_decoding
+            }
+        }
+    });
 
     let expanded = quote! {
 
         // The generated impl.
-        impl<'a> rlp::Decode<'a> for #name #ty_generics #where_clause {
+        impl #impl_generics rlp::Decode<#de> for #name #ty_generics #where_clause {
             const TYPE: rlp::ItemType = #item_type;
 
-            fn decode(payload: rlp::ItemPayloadSlice<'a>) -> Result<Self, rlp::Error> {
+            fn decode(payload: rlp::ItemPayloadSlice<#de>) -> Result<Self, rlp::Error> {
                 #decoding
             }
+
+            #decode_item
         }
     };
 
@@ -79,149 +208,409 @@ fn add_trait_encode_bounds(mut generics: Generics) -> Generics {
     generics
 }
 
-// Generate expressions to encode the fields and append the result to `output`.
-fn encode_struct_fields(data: &Data) -> TokenStream {
-    match *data {
-        Data::Struct(ref data) => {
-            match data.fields {
-                Fields::Named(ref fields) => {
-                    // Expands to expressions like
-                    //
-                    // ```
-                    // encode_to(&self.x, &mut payload);
-                    // encode_to(&self.y, &mut payload);
-                    // encode_to(&self.z, &mut payload);
-                    // ```
-                    //
-                    // but using fully qualified function call syntax.
-                    let recurse = fields.named.iter().map(|f| {
-                        let name = &f.ident;
-                        quote_spanned! {f.span()=>
-                            rlp::encode_to(&self.#name, &mut payload);
-                        }
-                    });
-                    quote! {
-                        let mut payload = vec![];
-                        #(#recurse)*
-
-                        rlp::ItemPayloadSlice(&payload).encode_as_list(output);
-                    }
-                }
-                Fields::Unnamed(ref fields) => {
-                    if fields.unnamed.len() == 1 {
-                        // For "newtype", encode as a single value
-                        let field = fields.unnamed.first().unwrap();
-                        quote_spanned! {field.span()=>
-                            rlp::encode_to(&self.0, output);
-                        }
-                    } else {
-                        // tuple struct not supported
-                        unimplemented!();
-                    }
-                }
-                Fields::Unit => unimplemented!(),
+// Generate expressions to encode the fields and append the result to `output`,
+// and an expression of the length in bytes of the encoding.
+fn encode_struct_fields(data: &DataStruct) -> syn::Result<(TokenStream, TokenStream)> {
+    match data.fields {
+        Fields::Named(ref fields) => {
+            let values = fields.named.iter().map(|f| {
+                let name = &f.ident;
+                quote!(&self.#name)
+            });
+            encode_list_fields(&data.fields, values, quote!(output))
+        }
+        Fields::Unnamed(ref fields) => {
+            if fields.unnamed.len() == 1 {
+                // For "newtype", encode as a single value
+                let field = fields.unnamed.first().unwrap();
+                check_no_field_attrs(field)?;
+                Ok((
+                    quote_spanned! {field.span()=>
+                        rlp::encode_to(&self.0, output);
+                    },
+                    quote_spanned! {field.span()=>
+                        rlp::encoded_len(&self.0)
+                    },
+                ))
+            } else {
+                // For tuple struct, encode as a list like named fields
+                let values = (0..fields.unnamed.len()).map(|i| {
+                    let index = Index::from(i);
+                    quote!(&self.#index)
+                });
+                encode_list_fields(&data.fields, values, quote!(output))
             }
         }
-        Data::Enum(_) | Data::Union(_) => unimplemented!(),
+        // For unit struct, encode as an empty list
+        Fields::Unit => encode_list_fields(&data.fields, std::iter::empty(), quote!(output)),
+    }
+}
+
+// Returns `generics` with the lifetime of the decoding data, and the lifetime.
+//
+// The only lifetime of `generics` is used as it is. Otherwise a new lifetime
+// is added, which the lifetimes of `generics` are bound to be equal to, so
+// the fields can borrow the decoding data whatever their lifetimes.
+fn add_decoding_lifetime(generics: &Generics) -> (Generics, Lifetime) {
+    let lifetimes: Vec<Lifetime> = generics
+        .lifetimes()
+        .map(|param| param.lifetime.clone())
+        .collect();
+    if let [lifetime] = &lifetimes[..] {
+        return (generics.clone(), lifetime.clone());
+    }
+
+    let mut generics = generics.clone();
+    let de = Lifetime::new("'__de", Span::call_site());
+    generics.params.insert(0, parse_quote!(#de));
+    let where_clause = generics.make_where_clause();
+    for lifetime in lifetimes {
+        where_clause.predicates.push(parse_quote!(#de: #lifetime));
+        where_clause.predicates.push(parse_quote!(#lifetime: #de));
     }
+    (generics, de)
 }
 
-// Add a bound `T: Decode` to every type parameter T.
-fn add_trait_decode_bounds(mut generics: Generics) -> Generics {
+// Add a bound `T: Decode<'de>` to every type parameter T.
+fn add_trait_decode_bounds(mut generics: Generics, de: &Lifetime) -> Generics {
     for param in &mut generics.params {
         if let GenericParam::Type(ref mut type_param) = *param {
-            type_param.bounds.push(parse_quote!(rlp::Decode));
+            type_param.bounds.push(parse_quote!(rlp::Decode<#de>));
         }
     }
     generics
 }
 
 // Generate expressions to decode the fields and create a new `#name`.
-fn decode_struct_fields(name: &Ident, data: &Data) -> TokenStream {
-    match *data {
-        Data::Struct(ref data) => {
-            match data.fields {
-                Fields::Named(ref fields) => {
-                    // Expands to expressions like
-                    //
-                    // ```
-                    // let a: TYPE_A = list_iter.next_item()?;
-                    // let b: TYPE_B = list_iter.next_item()?;
-                    // let c: TYPE_C = list_iter.next_item()?;
-                    // ```
-                    let recurse1 = fields.named.iter().map(|f| {
-                        let name = &f.ident;
-                        let ty = &f.ty;
-                        quote_spanned! {f.span()=>
-                            let #name: #ty = list_iter.next_item()?;
-                        }
-                    });
-
-                    // Expands to a expression like
-                    //
-                    // ```
-                    // a, b, c
-                    // ```
-                    let recurse2 = fields.named.iter().map(|f| {
-                        let name = &f.ident;
-                        quote! {
-                            #name
-                        }
-                    });
-                    quote! {
-                        let mut list_iter = payload.list_iter_unchecked();
-                        #(#recurse1)*
-
-                        if list_iter.next().is_some() {
-                            return Err(rlp::Error::ListDecodingNumberDoesNotMatch);
-                        }
-
-                        Ok(#name { #(#recurse2),* })
-                    }
-                }
-                Fields::Unnamed(ref fields) => {
-                    if fields.unnamed.len() == 1 {
-                        // Decode "newtype" as a single value
-                        let field = fields.unnamed.first().unwrap();
-                        let ty = &field.ty;
-                        quote! {
-                            let value = <#ty as rlp::Decode>::decode(payload)?;
-                            Ok(#name (value))
-                        }
-                    } else {
-                        // tuple struct not supported
-                        unimplemented!();
-                    }
-                }
-                Fields::Unit => unimplemented!(),
+fn decode_struct_fields(name: &Ident, data: &DataStruct) -> syn::Result<TokenStream> {
+    match data.fields {
+        Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+            // Decode "newtype" as a single value
+            let field = fields.unnamed.first().unwrap();
+            check_no_field_attrs(field)?;
+            let ty = &field.ty;
+            Ok(quote! {
+                let value = <#ty as rlp::Decode>::decode(payload)?;
+                Ok(#name (value))
+            })
+        }
+        _ => {
+            let decoding = decode_list_fields(
+                &quote!(#name),
+                &name.to_string(),
+                &data.fields,
+                quote!(payload),
+            )?;
+            Ok(quote! {
+                Ok(#decoding)
+            })
+        }
+    }
+}
+
+// Generate an expression of the item type the struct is encoded as.
+fn encoding_struct_item_type(data: &DataStruct) -> TokenStream {
+    match data.fields {
+        Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+            let ty = &fields.unnamed.first().unwrap().ty;
+            quote! {
+                <#ty as rlp::Encode>::encoded_type()
             }
         }
-        Data::Enum(_) | Data::Union(_) => unimplemented!(),
+        _ => quote! {
+            rlp::ItemType::List
+        },
+    }
+}
+
+// Generate an expression to decode a "newtype" from the whole item, `None`
+// for the other structs.
+fn decode_struct_item(name: &Ident, data: &DataStruct) -> Option<TokenStream> {
+    match data.fields {
+        Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+            let ty = &fields.unnamed.first().unwrap().ty;
+            Some(quote! {
+                let value = <#ty as rlp::Decode>::decode_item(item_type, item_data, payload)?;
+                Ok(#name (value))
+            })
+        }
+        _ => None,
     }
 }
 
 // Generate item type
-fn decoding_struct_item_type(data: &Data) -> TokenStream {
-    match *data {
-        Data::Struct(ref data) => match data.fields {
-            Fields::Named(_) => {
+fn decoding_struct_item_type(data: &DataStruct) -> TokenStream {
+    match data.fields {
+        Fields::Named(_) => {
+            quote! {
+                rlp::ItemType::List
+            }
+        }
+        Fields::Unnamed(ref fields) => {
+            if fields.unnamed.len() == 1 {
+                // "newtype" should be represented as its only field
+                let ty = &fields.unnamed.first().unwrap().ty;
+                quote! {
+                    <#ty as rlp::Decode>::TYPE
+                }
+            } else {
+                // tuple struct should be represented as a list
                 quote! {
                     rlp::ItemType::List
                 }
             }
-            Fields::Unnamed(ref fields) => {
-                if fields.unnamed.len() == 1 {
-                    // "newtype" should be represented as a single value
-                    quote! {
-                        rlp::ItemType::SingleValue
-                    }
-                } else {
-                    // tuple struct not supported
-                    unimplemented!();
-                }
+        }
+        Fields::Unit => {
+            quote! {
+                rlp::ItemType::List
             }
-            Fields::Unit => unimplemented!(),
+        }
+    }
+}
+
+// Returns the names of the variables which `fields` are bound to.
+//
+// The names of the named fields are used as they are, unnamed fields are
+// bound to `f0`, `f1`, `f2` and so on.
+pub(crate) fn field_bindings(fields: &Fields) -> Vec<Ident> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| match f.ident {
+            Some(ref ident) => ident.clone(),
+            None => format_ident!("f{}", i),
+        })
+        .collect()
+}
+
+// Generate an expression (or pattern) to create `path` from the variables
+// `bindings` which `fields` are bound to.
+//
+// Expands to `path { a, b, c }`, `path(f0, f1, f2)` or `path`.
+pub(crate) fn construct(path: &TokenStream, fields: &Fields, bindings: &[Ident]) -> TokenStream {
+    match fields {
+        Fields::Named(_) => quote! {
+            #path { #(#bindings),* }
         },
-        Data::Enum(_) | Data::Union(_) => unimplemented!(),
+        Fields::Unnamed(_) => quote! {
+            #path(#(#bindings),*)
+        },
+        Fields::Unit => quote! {
+            #path
+        },
+    }
+}
+
+// Generate expressions to encode `fields` as a list and append the result to
+// `output`, and an expression of the length in bytes of the encoding.
+//
+// `values` are the expressions of the references to `fields`.
+pub(crate) fn encode_list_fields(
+    fields: &Fields,
+    values: impl Iterator<Item = TokenStream>,
+    output: TokenStream,
+) -> syn::Result<(TokenStream, TokenStream)> {
+    let all_attrs = parse_list_field_attrs(fields)?;
+    let values: Vec<_> = values.collect();
+
+    let payload_len = list_fields_payload_len(fields, &all_attrs, &values);
+
+    // Expands to expressions like
+    //
+    // ```
+    // rlp::encode_to(&self.x, output);
+    // rlp::encode_to(&self.y, output);
+    // rlp::encode_to(&self.z, output);
+    // ```
+    //
+    // or the functions of the module specified by `with`.
+    let items = list_fields_statements(fields, &all_attrs, &values, |f, with, value| {
+        let encode_to = match with {
+            Some(module) => quote!(#module::encode_to),
+            None => quote!(rlp::encode_to),
+        };
+        quote_spanned! {f.span()=>
+            #encode_to(#value, #output);
+        }
+    });
+
+    let encoding = quote! {
+        let payload_len = #payload_len;
+        rlp::encode_header(rlp::ItemType::List, payload_len, #output);
+        #items
+    };
+    let encoded_len = quote! {
+        {
+            let payload_len = #payload_len;
+            rlp::encoded_header_len(payload_len) + payload_len
+        }
+    };
+    Ok((encoding, encoded_len))
+}
+
+// Generate an expression of the length in bytes of the list payload of
+// `fields`.
+fn list_fields_payload_len(
+    fields: &Fields,
+    all_attrs: &[FieldAttrs],
+    values: &[TokenStream],
+) -> TokenStream {
+    // Expands to expressions like
+    //
+    // ```
+    // payload_len += rlp::encoded_len(&self.x);
+    // ```
+    let lens = list_fields_statements(fields, all_attrs, values, |f, with, value| {
+        let encoded_len = match with {
+            Some(module) => quote!(#module::encoded_len),
+            None => quote!(rlp::encoded_len),
+        };
+        quote_spanned! {f.span()=>
+            payload_len += #encoded_len(#value);
+        }
+    });
+
+    quote! {
+        {
+            #[allow(unused_mut)]
+            let mut payload_len: usize = 0;
+            #lens
+            payload_len
+        }
     }
 }
+
+// Generate the statements `statement` creates for the values of `fields`
+// which are encoded as list items.
+//
+// `statement` is called with the field, the module specified by `with` and
+// the expression of the reference to the value to encode. The `trailing`
+// fields are placed in a block which ends at the first `None`.
+fn list_fields_statements(
+    fields: &Fields,
+    all_attrs: &[FieldAttrs],
+    values: &[TokenStream],
+    statement: impl Fn(&Field, Option<&Path>, TokenStream) -> TokenStream,
+) -> TokenStream {
+    let mut required = vec![];
+    let mut optional = vec![];
+    for ((f, attrs), value) in fields.iter().zip(all_attrs).zip(values) {
+        if attrs.skip {
+            continue;
+        }
+
+        let with = attrs.with.as_ref();
+        if attrs.trailing {
+            // Ends the list at the first `None`.
+            let statement = statement(f, with, quote!(value));
+            optional.push(quote_spanned! {f.span()=>
+                match #value {
+                    Some(value) => { #statement }
+                    None => break 'trailing,
+                }
+            });
+        } else if attrs.default {
+            optional.push(statement(f, with, value.clone()));
+        } else {
+            required.push(statement(f, with, value.clone()));
+        }
+    }
+
+    if all_attrs.iter().any(|attrs| attrs.trailing) {
+        quote! {
+            #(#required)*
+            'trailing: {
+                #(#optional)*
+            }
+        }
+    } else {
+        quote! {
+            #(#required)*
+            #(#optional)*
+        }
+    }
+}
+
+// Generate an expression to decode `fields` from the items of the list
+// `payload`, and create `path`.
+//
+// The errors of the fields are recorded as occurring in the fields of
+// `type_name`, see `rlp::Error::in_field`.
+pub(crate) fn decode_list_fields(
+    path: &TokenStream,
+    type_name: &str,
+    fields: &Fields,
+    payload: TokenStream,
+) -> syn::Result<TokenStream> {
+    let all_attrs = parse_list_field_attrs(fields)?;
+
+    // Expands to expressions like
+    //
+    // ```
+    // let a: TYPE_A = list_iter.next_item().map_err(|e| e.in_field("T", "a"))?;
+    // let b: TYPE_B = list_iter.next_item().map_err(|e| e.in_field("T", "b"))?;
+    // let c: TYPE_C = list_iter.next_item().map_err(|e| e.in_field("T", "c"))?;
+    // ```
+    //
+    // or matching `list_iter.next_item_with(...)` to handle the attributes.
+    let bindings = field_bindings(fields);
+    let recurse = fields
+        .iter()
+        .zip(&all_attrs)
+        .zip(&bindings)
+        .enumerate()
+        .map(|(i, ((f, attrs), binding))| {
+            let ty = &f.ty;
+            if attrs.skip {
+                return quote_spanned! {f.span()=>
+                    let #binding: #ty = Default::default();
+                };
+            }
+
+            let field_name = match f.ident {
+                Some(ref ident) => ident.unraw().to_string(),
+                None => i.to_string(),
+            };
+            let decoded = if !attrs.is_optional() && attrs.with.is_none() {
+                quote!(list_iter.next_item())
+            } else {
+                let decode = match attrs.with {
+                    Some(ref module) => quote!(#module::decode),
+                    None => quote!(rlp::Decode::decode_item),
+                };
+                let (decoded, absent) = if attrs.trailing {
+                    (quote!(result.map(Some)), quote!(Ok(None)))
+                } else if attrs.default {
+                    (quote!(result), quote!(Ok(Default::default())))
+                } else {
+                    (
+                        quote!(result),
+                        quote!(Err(rlp::Error::ListDecodingIterationEnded)),
+                    )
+                };
+                quote! {
+                    match list_iter.next_item_with(#decode) {
+                        Some(result) => #decoded,
+                        None => #absent,
+                    }
+                }
+            };
+            quote_spanned! {f.span()=>
+                let #binding: #ty = #decoded.map_err(|e| e.in_field(#type_name, #field_name))?;
+            }
+        });
+    let construction = construct(path, fields, &bindings);
+
+    Ok(quote! {
+        {
+            let mut list_iter = #payload.list_iter_unchecked();
+            #(#recurse)*
+
+            if list_iter.next().is_some() {
+                return Err(rlp::Error::ListDecodingNumberDoesNotMatch);
+            }
+
+            #construction
+        }
+    })
+}
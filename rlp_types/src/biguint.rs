@@ -6,17 +6,24 @@
 
 //! Implements RLP for BigUint.
 
-use extensions::strip_left_padding;
+#![allow(deprecated)]
+
 use num_bigint::BigUint;
-use num_traits::Zero;
-use rlp::{Decode, Encode, Error, ItemPayloadSlice, ItemType};
+use rlp::{Decode, Encode, Error, ItemPayloadSlice, ItemType, Output};
 
+/// A `BigUint` wrapper, kept for compatibility now that `rlp` implements RLP
+/// for `BigUint` with its `num-bigint` feature.
+#[deprecated(note = "use `num_bigint::BigUint` with the `num-bigint` feature of `rlp`")]
 #[derive(Debug, PartialEq)]
 pub struct RlpBigUint(pub BigUint);
 
 impl Encode for RlpBigUint {
-    fn encode_to(&self, output: &mut Vec<u8>) {
-        ItemPayloadSlice(strip_left_padding(&self.0.to_bytes_be())).encode_as_single_value(output);
+    fn encode_to<O: Output + ?Sized>(&self, output: &mut O) {
+        self.0.encode_to(output);
+    }
+
+    fn encoded_len(&self) -> usize {
+        self.0.encoded_len()
     }
 }
 
@@ -24,14 +31,7 @@ impl<'a> Decode<'a> for RlpBigUint {
     const TYPE: ItemType = ItemType::SingleValue;
 
     fn decode(payload: ItemPayloadSlice<'a>) -> Result<Self, Error> {
-        if payload.0.is_empty() {
-            return Ok(RlpBigUint(BigUint::zero()));
-        }
-        if *payload.0.first().unwrap() == 0 {
-            return Err(Error::UintDecodingFoundLeftPadding);
-        }
-
-        Ok(RlpBigUint(BigUint::from_bytes_be(payload.0)))
+        BigUint::decode(payload).map(RlpBigUint)
     }
 }
 
@@ -0,0 +1,422 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements the bracket notation of RLP, as used in the spec and the
+//! comments of the tests, e.g. `[ "cat", [1, 0x0400], [] ]`.
+//!
+//! - a list is written as its items separated by commas in square brackets,
+//!   a trailing comma is allowed
+//! - a string is written in double quotes, with the escapes `\"`, `\\`,
+//!   `\n`, `\t` and `\xNN` for any byte
+//! - bytes are written in hex with the prefix `0x`, `0x` alone being the
+//!   empty string
+//! - an unsigned integer of any size is written in decimal
+//!
+//! `format_bracket_notation` writes RLP back in the notation, which parses to
+//! the same RLP.
+
+use num_bigint::BigUint;
+use rlp::{Error, ItemDataSlice, ItemPayloadSlice, ItemType, Limit, RlpStream};
+
+/// The maximum nesting depth of lists parsed by `parse_bracket_notation` and
+/// formatted by `format_bracket_notation`.
+const MAX_DEPTH: usize = 1024;
+
+/// Parses the bracket notation literal `$s` to RLP, see
+/// `parse_bracket_notation`.
+///
+/// # Panics
+///
+/// Will panic if `$s` isn't valid bracket notation.
+///
+/// # Examples
+///
+/// ```
+/// use rlp_types::bracket;
+///
+/// assert_eq!(bracket!(r#"["cat", [1024]]"#), [0xc8, 0x83, b'c', b'a', b't', 0xc3, 0x82, 0x04, 0x00]);
+/// ```
+#[macro_export]
+macro_rules! bracket {
+    ($s:expr) => {
+        $crate::bracket::parse_bracket_notation($s).expect("invalid bracket notation")
+    };
+}
+
+/// An error of parsing the bracket notation, with the offset in bytes into
+/// the input where it occurred.
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum ParseError {
+    #[error("unexpected end of input")]
+    UnexpectedEnd,
+    #[error("unexpected character {0:?} at {1}")]
+    UnexpectedCharacter(char, usize),
+    #[error("invalid hex at {0}")]
+    InvalidHex(usize),
+    #[error("invalid escape at {0}")]
+    InvalidEscape(usize),
+    #[error("lists nested too deeply at {0}")]
+    MaximumDepthExceeded(usize),
+}
+
+/// Parses the bracket notation `s` and returns the RLP encoding.
+///
+/// # Examples
+///
+/// ```
+/// use rlp_types::bracket::parse_bracket_notation;
+///
+/// let encoded = parse_bracket_notation("[ [], [[]], [ [], [[]] ] ]").unwrap();
+/// assert_eq!(hex::encode(&encoded), "c7c0c1c0c3c0c1c0");
+///
+/// let encoded = parse_bracket_notation(r#"["cat", 1024, 0x01ff]"#).unwrap();
+/// assert_eq!(hex::encode(&encoded), "ca836361748204008201ff");
+/// ```
+pub fn parse_bracket_notation(s: &str) -> Result<Vec<u8>, ParseError> {
+    let mut parser = Parser {
+        input: s.as_bytes(),
+        position: 0,
+        depth: 0,
+        stream: RlpStream::new(),
+    };
+    parser.skip_whitespace();
+    parser.parse_value()?;
+    parser.skip_whitespace();
+    if let Some(c) = parser.peek_char() {
+        return Err(ParseError::UnexpectedCharacter(c, parser.position));
+    }
+    Ok(parser.stream.out())
+}
+
+/// Formats the RLP `data` in the bracket notation.
+///
+/// A single value is written as a string if it is printable ASCII, and as
+/// hex otherwise, e.g. an integer. Parsing the result returns `data`, which
+/// must be canonically encoded.
+///
+/// # Examples
+///
+/// ```
+/// use rlp_types::bracket::format_bracket_notation;
+///
+/// let data = hex::decode("ca836361748204008201ff").unwrap();
+/// assert_eq!(format_bracket_notation(&data).unwrap(), r#"["cat", 0x0400, 0x01ff]"#);
+/// ```
+pub fn format_bracket_notation(data: &[u8]) -> Result<String, Error> {
+    let (item_type, payload) = ItemDataSlice(data).as_payload()?;
+    let mut s = String::new();
+    format_item(item_type, payload, 0, &mut s)?;
+    Ok(s)
+}
+
+// Formats the item of `item_type` and `payload`, whose parent list is at
+// `depth`, and appends the result to `s`.
+fn format_item(
+    item_type: ItemType,
+    payload: ItemPayloadSlice,
+    depth: usize,
+    s: &mut String,
+) -> Result<(), Error> {
+    let bytes = payload.0;
+    match item_type {
+        ItemType::SingleValue
+            if !bytes.is_empty() && bytes.iter().all(|b| (0x20..0x7f).contains(b)) =>
+        {
+            s.push('"');
+            for &b in bytes {
+                if b == b'"' || b == b'\\' {
+                    s.push('\\');
+                }
+                s.push(b as char);
+            }
+            s.push('"');
+        }
+        ItemType::SingleValue if bytes.is_empty() => s.push_str(r#""""#),
+        ItemType::SingleValue => {
+            s.push_str("0x");
+            s.push_str(&hex::encode(bytes));
+        }
+        ItemType::List => {
+            if depth == MAX_DEPTH {
+                return Err(Error::LimitExceeded(Limit::Depth));
+            }
+            s.push('[');
+            for (i, result) in payload.list_iter_unchecked().enumerate() {
+                let (item_type, payload) = result?;
+                if i > 0 {
+                    s.push_str(", ");
+                }
+                format_item(item_type, payload, depth + 1, s)?;
+            }
+            s.push(']');
+        }
+    }
+    Ok(())
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    // The offset of the next byte to parse in `input`.
+    position: usize,
+    // The number of the lists being parsed.
+    depth: usize,
+    stream: RlpStream,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.position).copied()
+    }
+
+    // Returns the next character, which may be non-ASCII, for errors.
+    fn peek_char(&self) -> Option<char> {
+        let rest = &self.input[self.position..];
+        let len = rest.len().min(4);
+        (1..=len)
+            .find_map(|n| std::str::from_utf8(&rest[..n]).ok())
+            .and_then(|s| s.chars().next())
+    }
+
+    fn unexpected(&self) -> ParseError {
+        match self.peek_char() {
+            Some(c) => ParseError::UnexpectedCharacter(c, self.position),
+            None => ParseError::UnexpectedEnd,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|b| b.is_ascii_whitespace()) {
+            self.position += 1;
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(b'[') => self.parse_list(),
+            Some(b'"') => self.parse_string(),
+            Some(b'0') if self.input.get(self.position + 1) == Some(&b'x') => self.parse_hex(),
+            Some(b'0'..=b'9') => self.parse_integer(),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn parse_list(&mut self) -> Result<(), ParseError> {
+        if self.depth == MAX_DEPTH {
+            return Err(ParseError::MaximumDepthExceeded(self.position));
+        }
+        self.depth += 1;
+        // Skips `[`.
+        self.position += 1;
+        self.stream.begin_list();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(b']') {
+                break;
+            }
+            self.parse_value()?;
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b']') => break,
+                _ => return Err(self.unexpected()),
+            }
+        }
+        // Skips `]`.
+        self.position += 1;
+        self.stream.end_list();
+        self.depth -= 1;
+        Ok(())
+    }
+
+    fn parse_string(&mut self) -> Result<(), ParseError> {
+        // Skips `"`.
+        self.position += 1;
+        let mut bytes = vec![];
+        loop {
+            let b = self.peek().ok_or(ParseError::UnexpectedEnd)?;
+            self.position += 1;
+            match b {
+                b'"' => break,
+                b'\\' => {
+                    let escape = self.position - 1;
+                    let b = match self.peek() {
+                        Some(b'"') => b'"',
+                        Some(b'\\') => b'\\',
+                        Some(b'n') => b'\n',
+                        Some(b't') => b'\t',
+                        Some(b'x') => {
+                            let digits = self
+                                .input
+                                .get(self.position + 1..self.position + 3)
+                                .ok_or(ParseError::InvalidEscape(escape))?;
+                            self.position += 2;
+                            let mut byte = [0];
+                            hex::decode_to_slice(digits, &mut byte)
+                                .map_err(|_| ParseError::InvalidEscape(escape))?;
+                            byte[0]
+                        }
+                        _ => return Err(ParseError::InvalidEscape(escape)),
+                    };
+                    self.position += 1;
+                    bytes.push(b);
+                }
+                b => bytes.push(b),
+            }
+        }
+        self.stream.append(&bytes.as_slice());
+        Ok(())
+    }
+
+    fn parse_hex(&mut self) -> Result<(), ParseError> {
+        let start = self.position;
+        // Skips `0x`.
+        self.position += 2;
+        let digits_start = self.position;
+        while self.peek().is_some_and(|b| b.is_ascii_hexdigit()) {
+            self.position += 1;
+        }
+        let bytes = hex::decode(&self.input[digits_start..self.position])
+            .map_err(|_| ParseError::InvalidHex(start))?;
+        self.stream.append(&bytes.as_slice());
+        Ok(())
+    }
+
+    fn parse_integer(&mut self) -> Result<(), ParseError> {
+        let start = self.position;
+        while self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.position += 1;
+        }
+        let n = BigUint::parse_bytes(&self.input[start..self.position], 10)
+            .expect("decimal digits are valid");
+        self.stream.append(&n);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let test_data = [
+            ("[]", "c0"),
+            ("[ [], [[]], [ [], [[]] ] ]", "c7c0c1c0c3c0c1c0"),
+            (r#""dog""#, "83646f67"),
+            (r#""""#, "80"),
+            (r#""\x00""#, "00"),
+            (r#""a\"\\\n""#, "84 61225c0a"),
+            ("0x", "80"),
+            ("0x7f", "7f"),
+            ("0x80", "8180"),
+            ("0", "80"),
+            ("15", "0f"),
+            ("1024", "820400"),
+            (
+                "83729609699884896815286331701780722",
+                "8f102030405060708090a0b0c0d0e0f2",
+            ),
+            (r#" [ "cat", "dog", ] "#, "c8 83636174 83646f67"),
+        ];
+
+        for (s, encoded) in test_data {
+            assert_eq!(
+                hex::encode(parse_bracket_notation(s).unwrap()),
+                encoded.replace(' ', ""),
+                "{s}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let test_data = [
+            ("", ParseError::UnexpectedEnd),
+            ("[1, 2", ParseError::UnexpectedEnd),
+            ("[1 2]", ParseError::UnexpectedCharacter('2', 3)),
+            ("[,]", ParseError::UnexpectedCharacter(',', 1)),
+            ("[] []", ParseError::UnexpectedCharacter('[', 3)),
+            ("-1", ParseError::UnexpectedCharacter('-', 0)),
+            ("[é]", ParseError::UnexpectedCharacter('é', 1)),
+            (r#""abc"#, ParseError::UnexpectedEnd),
+            (r#""\q""#, ParseError::InvalidEscape(1)),
+            (r#""\x0g""#, ParseError::InvalidEscape(1)),
+            ("[0x123]", ParseError::InvalidHex(1)),
+        ];
+
+        for (s, err) in test_data {
+            assert_eq!(parse_bracket_notation(s).unwrap_err(), err, "{s}");
+        }
+    }
+
+    #[test]
+    fn test_parse_depth() {
+        let s = format!("{}{}", "[".repeat(MAX_DEPTH), "]".repeat(MAX_DEPTH));
+        let encoded = parse_bracket_notation(&s).unwrap();
+        assert_eq!(format_bracket_notation(&encoded).unwrap(), s);
+
+        let s = format!("{}{}", "[".repeat(MAX_DEPTH + 1), "]".repeat(MAX_DEPTH + 1));
+        assert_eq!(
+            parse_bracket_notation(&s).unwrap_err(),
+            ParseError::MaximumDepthExceeded(MAX_DEPTH)
+        );
+        // It fails before running out of stack.
+        assert_eq!(
+            parse_bracket_notation(&"[".repeat(1 << 24)).unwrap_err(),
+            ParseError::MaximumDepthExceeded(MAX_DEPTH)
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        let test_data = [
+            (&hex!("c0") as &[u8], "[]"),
+            (&hex!("c7c0c1c0c3c0c1c0"), "[[], [[]], [[], [[]]]]"),
+            (&hex!("83646f67"), r#""dog""#),
+            (&hex!("80"), r#""""#),
+            (&hex!("00"), "0x00"),
+            (&hex!("0f"), "0x0f"),
+            (&hex!("820400"), "0x0400"),
+            (&hex!("84 61225c20"), r#""a\"\\ ""#),
+            (&hex!("c5 83636174 0a"), r#"["cat", 0x0a]"#),
+        ];
+
+        for (data, s) in test_data {
+            assert_eq!(format_bracket_notation(data).unwrap(), s, "{data:x?}");
+            assert_eq!(parse_bracket_notation(s).unwrap(), data, "{s}");
+        }
+    }
+
+    #[test]
+    fn test_format_errors() {
+        assert_eq!(
+            format_bracket_notation(&hex!("c28100")).unwrap_err(),
+            Error::SingleByteEncodedAsTwo
+        );
+        assert_eq!(
+            format_bracket_notation(&hex!("c3c30102")).unwrap_err(),
+            Error::ItemDataWithInvalidByteLength
+        );
+
+        let mut data = vec![0xc0];
+        for _ in 0..MAX_DEPTH {
+            let mut header = vec![];
+            rlp::encode_header(ItemType::List, data.len(), &mut header);
+            data = [header, data].concat();
+        }
+        assert_eq!(
+            format_bracket_notation(&data).unwrap_err(),
+            Error::LimitExceeded(Limit::Depth)
+        );
+    }
+
+    #[test]
+    fn test_macro() {
+        assert_eq!(bracket!("[ [], [[]] ]"), hex!("c3c0c1c0"));
+    }
+}
@@ -10,10 +10,9 @@
 //! For example, the RLP string b"abc" will be represented as the JSON string
 //! "0x616263". It applies to both JSON => RLP and RLP => JSON.
 
-use std::borrow::Cow;
-
-use rlp::{encode_to, Error, ItemDataSlice, ItemPayloadSlice, ItemType};
-use serde_json::Value;
+use num_bigint::BigUint;
+use rlp::{Error, ItemDataSlice, ItemPayloadSlice, ItemType, RlpStream};
+use serde_json::{Number, Value};
 
 /// Decodes RLP `data` to a JSON `Value`.
 ///
@@ -56,8 +55,25 @@ fn decode_payload_to_json_value(
     }
 }
 
+/// An error of encoding a JSON `Value` to RLP.
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum EncodingError {
+    #[error("JSON null is not supported")]
+    Null,
+    #[error("invalid number {0}, expected an unsigned integer")]
+    InvalidNumber(Number),
+    #[error("invalid decimal string {0:?}")]
+    InvalidDecimalString(String),
+    #[error("invalid hex string {0:?}")]
+    InvalidHexString(String),
+}
+
 /// Encodes JSON `value` to RLP and returns the output.
 ///
+/// # Panics
+///
+/// Panics if `value` can't be encoded, see `try_encode_json_value_to_rlp`.
+///
 /// # Examples
 ///
 /// ```
@@ -69,32 +85,215 @@ fn decode_payload_to_json_value(
 /// assert_eq!(hex::encode(&encoded), "c7c0c1c0c3c0c1c0");
 /// ```
 pub fn encode_json_value_to_rlp(value: &Value) -> Vec<u8> {
-    let mut output = vec![];
+    try_encode_json_value_to_rlp(value).unwrap()
+}
+
+/// Encodes JSON `value` to RLP and returns the output, or an error if `value`
+/// can't be encoded.
+///
+/// The JSON values are mapped to RLP as:
+///
+/// - an unsigned integer number to an unsigned integer, of any size with the
+///   `arbitrary_precision` feature or else up to `u64::MAX`, other numbers
+///   are errors
+/// - a string prefixed with `#` to an unsigned integer of any size, e.g.
+///   `"#83729609699884896815286331701780722"`, as in the Ethereum RLP tests
+/// - a string prefixed with `0x` to the bytes it represents in hex
+/// - another string to its UTF-8 bytes
+/// - `true` to `0x01`, and `false` to `0x80`, as `bool`
+/// - an array to a list of its elements
+/// - an object to a list of `[key, value]` lists in the iteration order of
+///   the object, i.e. sorted by key unless `serde_json` preserves the order;
+///   the keys are mapped as strings
+/// - null is an error
+///
+/// # Examples
+///
+/// ```
+/// use rlp_types::json::{try_encode_json_value_to_rlp, EncodingError};
+/// use serde_json::json;
+///
+/// let v = json!({ "a": true, "b": "#65536" });
+/// let encoded = try_encode_json_value_to_rlp(&v).unwrap();
+/// assert_eq!(hex::encode(&encoded), "c9c26101c56283010000");
+///
+/// let v = json!([1, -1]);
+/// assert!(matches!(
+///     try_encode_json_value_to_rlp(&v),
+///     Err(EncodingError::InvalidNumber(_))
+/// ));
+/// ```
+pub fn try_encode_json_value_to_rlp(value: &Value) -> Result<Vec<u8>, EncodingError> {
+    let mut stream = RlpStream::new();
+    append_json_value(&mut stream, value)?;
+    Ok(stream.out())
+}
+
+/// Encodes JSON `value` to RLP and appends the result to `stream`.
+fn append_json_value(stream: &mut RlpStream, value: &Value) -> Result<(), EncodingError> {
     match value {
-        Value::Number(number) => {
-            let n = number.as_u64().unwrap();
-            encode_to(&n, &mut output);
+        Value::Null => return Err(EncodingError::Null),
+        Value::Bool(b) => {
+            stream.append(b);
         }
-        Value::String(string) => {
-            let bytes = string.as_bytes();
-            // Converts hex format string to bytes
-            let bytes: Cow<[u8]> = if let Some(s) = bytes.strip_prefix(b"0x") {
-                hex::decode(s).unwrap().into()
-            } else {
-                bytes.into()
-            };
-            encode_to(&bytes.as_ref(), &mut output);
+        Value::Number(number) => {
+            // `serde_json` keeps the literal of the number with the
+            // `arbitrary_precision` feature, which is parsed as is so numbers
+            // above `u64::MAX` are exact. Otherwise such a number is a float,
+            // which is rejected.
+            let literal = number.to_string();
+            let n = literal
+                .bytes()
+                .all(|b| b.is_ascii_digit())
+                .then(|| BigUint::parse_bytes(literal.as_bytes(), 10))
+                .flatten()
+                .ok_or_else(|| EncodingError::InvalidNumber(number.clone()))?;
+            stream.append(&n);
         }
+        Value::String(string) => append_json_string(stream, string)?,
         Value::Array(array) => {
-            let mut payload = vec![];
-            array.iter().for_each(|element| {
-                let element_rlp = encode_json_value_to_rlp(element);
-                payload.extend(element_rlp);
-            });
-            ItemPayloadSlice(&payload).encode_as_list(&mut output);
+            stream.begin_list();
+            for element in array {
+                append_json_value(stream, element)?;
+            }
+            stream.end_list();
         }
-        Value::Null | Value::Bool(_) | Value::Object(_) => unimplemented!(),
+        Value::Object(object) => {
+            stream.begin_list();
+            for (key, value) in object {
+                stream.begin_list();
+                append_json_string(stream, key)?;
+                append_json_value(stream, value)?;
+                stream.end_list();
+            }
+            stream.end_list();
+        }
+    }
+    Ok(())
+}
+
+/// Encodes JSON `string` to RLP and appends the result to `stream`.
+fn append_json_string(stream: &mut RlpStream, string: &str) -> Result<(), EncodingError> {
+    if let Some(decimal) = string.strip_prefix('#') {
+        // `parse_bytes` accepts underscores, which are not decimal digits.
+        let n = decimal
+            .bytes()
+            .all(|b| b.is_ascii_digit())
+            .then(|| BigUint::parse_bytes(decimal.as_bytes(), 10))
+            .flatten()
+            .ok_or_else(|| EncodingError::InvalidDecimalString(string.to_string()))?;
+        stream.append(&n);
+    } else if let Some(hex_string) = string.strip_prefix("0x") {
+        // Converts hex format string to bytes
+        let bytes = hex::decode(hex_string)
+            .map_err(|_| EncodingError::InvalidHexString(string.to_string()))?;
+        stream.append(&bytes.as_slice());
+    } else {
+        stream.append(&string.as_bytes());
     }
+    Ok(())
+}
 
-    output
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_encode_json_values() {
+        let test_data = [
+            (json!(true), "01"),
+            (json!(false), "80"),
+            (json!(1024), "820400"),
+            (json!(0), "80"),
+            (json!("#0"), "80"),
+            (
+                json!("#83729609699884896815286331701780722"),
+                "8f102030405060708090a0b0c0d0e0f2",
+            ),
+            (json!("0x0400"), "820400"),
+            (json!("dog"), "83646f67"),
+            (json!({}), "c0"),
+            // [["cat", 1], ["dog", []]]
+            (
+                json!({ "dog": [], "cat": 1 }),
+                "ccc583636174 01c583646f67c0",
+            ),
+        ];
+
+        for (value, encoded) in test_data {
+            assert_eq!(
+                hex::encode(try_encode_json_value_to_rlp(&value).unwrap()),
+                encoded.replace(' ', ""),
+                "{value}"
+            );
+        }
+    }
+
+    #[cfg(feature = "arbitrary_precision")]
+    #[test]
+    fn test_encode_big_json_numbers() {
+        let test_data = [
+            ("18446744073709551615", "88ffffffffffffffff"),
+            // 2^64
+            ("18446744073709551616", "89010000000000000000"),
+            (
+                "[83729609699884896815286331701780722]",
+                "d08f102030405060708090a0b0c0d0e0f2",
+            ),
+        ];
+
+        for (json, encoded) in test_data {
+            let value: Value = serde_json::from_str(json).unwrap();
+            assert_eq!(
+                hex::encode(try_encode_json_value_to_rlp(&value).unwrap()),
+                encoded,
+                "{json}"
+            );
+        }
+    }
+
+    #[cfg(not(feature = "arbitrary_precision"))]
+    #[test]
+    fn test_big_json_numbers_without_arbitrary_precision() {
+        // 2^64
+        let value: Value = serde_json::from_str("18446744073709551616").unwrap();
+        assert!(matches!(
+            try_encode_json_value_to_rlp(&value),
+            Err(EncodingError::InvalidNumber(_))
+        ));
+    }
+
+    #[test]
+    fn test_encoding_errors() {
+        let test_data = [
+            (json!([1, null]), EncodingError::Null),
+            (json!(-1), EncodingError::InvalidNumber((-1).into())),
+            (
+                json!(1e3),
+                EncodingError::InvalidNumber(Number::from_f64(1e3).unwrap()),
+            ),
+            (
+                json!(1.5),
+                EncodingError::InvalidNumber(Number::from_f64(1.5).unwrap()),
+            ),
+            (
+                json!("#1_000"),
+                EncodingError::InvalidDecimalString("#1_000".to_string()),
+            ),
+            (
+                json!("#"),
+                EncodingError::InvalidDecimalString("#".to_string()),
+            ),
+            (
+                json!({ "a": "0x123" }),
+                EncodingError::InvalidHexString("0x123".to_string()),
+            ),
+        ];
+
+        for (value, err) in test_data {
+            assert_eq!(try_encode_json_value_to_rlp(&value).unwrap_err(), err);
+        }
+    }
 }
@@ -8,6 +8,8 @@
 //! testing.
 
 mod biguint;
+pub mod bracket;
 pub mod json;
 
+#[allow(deprecated)]
 pub use biguint::RlpBigUint;
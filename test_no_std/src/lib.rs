@@ -0,0 +1,60 @@
+// Copyright 2023 Developers of the Spimpl project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Derives RLP in a `no_std` crate, built in CI for a target without `std`
+//! to check the derived code only requires `core` and `alloc`.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use rlp::{Decode, Encode};
+
+#[derive(Encode, Decode)]
+pub struct Entry<'a> {
+    pub id: u64,
+    pub data: &'a [u8],
+    #[rlp(default)]
+    pub flags: Option<u16>,
+}
+
+#[derive(Encode, Decode)]
+pub struct Pair(pub u64, pub u64);
+
+#[derive(Encode, Decode)]
+pub struct Unit;
+
+#[derive(Encode, Decode)]
+pub enum Message<'a> {
+    Ping(u64),
+    Pong { request_id: &'a [u8], enr_seq: u64 },
+    Ack,
+}
+
+#[derive(Encode, Decode)]
+#[rlp(repr = "prefix")]
+pub enum Envelope<'a> {
+    #[rlp(tag = 1)]
+    Legacy(u64),
+    #[rlp(tag = 2)]
+    Typed(&'a [u8], u64),
+}
+
+#[derive(Encode, Decode)]
+#[rlp(repr = "untagged")]
+pub enum Value {
+    Number(u64),
+    Bytes(Vec<u8>),
+}
+
+#[derive(Encode, Decode)]
+#[rlp(repr = "untagged")]
+pub enum Shape {
+    Point(u16, u16),
+    Line { x1: u16, y1: u16, x2: u16, y2: u16 },
+}